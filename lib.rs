@@ -2,9 +2,10 @@
 
 #[ink::contract]
 mod treasury_governance {
-    use ink::prelude::{vec, vec::Vec, string::String};
+    use ink::prelude::{format, vec, vec::Vec, string::String};
     use ink::storage::Mapping;
-    use ink::primitives::H160;
+    use ink::primitives::{H160, H256};
+    use ink::U256;
 
     // Types
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -15,11 +16,15 @@ mod treasury_governance {
         Governance,
         Technical,
         Other,
+        /// Multi-seat election decided by approval ballots
+        Election,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    // Renaming the variants would break the contract's published metadata
+    #[allow(clippy::enum_variant_names)]
     pub enum VotingPeriod {
         ThreeDays,
         SevenDays,
@@ -28,6 +33,10 @@ mod treasury_governance {
     }
 
     impl VotingPeriod {
+        pub fn to_millis(&self) -> u64 {
+            u64::from(self.to_blocks()) * u64::from(DEFAULT_BLOCK_TIME_MS)
+        }
+
         pub fn to_blocks(&self) -> u32 {
             match self {
                 VotingPeriod::ThreeDays => 3 * 24 * 60 * 10,
@@ -70,6 +79,10 @@ mod treasury_governance {
     }
 
     impl ExecutionDelay {
+        pub fn to_millis(&self) -> u64 {
+            u64::from(self.to_blocks()) * u64::from(DEFAULT_BLOCK_TIME_MS)
+        }
+
         pub fn to_blocks(&self) -> u32 {
             match self {
                 ExecutionDelay::Immediately => 0,
@@ -80,6 +93,32 @@ mod treasury_governance {
         }
     }
 
+    /// Clock a proposal's voting end and execution time are measured on
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum SchedulingClock {
+        /// Block numbers, assuming the configured block time
+        BlockNumber,
+        /// Block timestamps in milliseconds, unaffected by stalled block production
+        Timestamp,
+    }
+
+    /// How a proposal is decided when several options share the highest vote count
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum TiePolicy {
+        /// Reject the proposal
+        Reject,
+        /// The tied option that reached its final count first wins
+        EarliestVoteWins,
+        /// The option the proposer voted for wins, if it is among the tied options
+        ProposerPreference,
+        /// Open a runoff proposal limited to the tied options
+        Runoff,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -87,6 +126,9 @@ mod treasury_governance {
         pub voting_period: VotingPeriod,
         pub quorum_threshold: QuorumThreshold,
         pub execution_delay: ExecutionDelay,
+        pub tie_policy: TiePolicy,
+        /// Finalize before `voting_end` once remaining voters can no longer change the outcome
+        pub early_decision: bool,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -104,15 +146,472 @@ mod treasury_governance {
         pub option_text: String,
     }
 
+    /// Asset a treasury spend is paid in
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum Asset {
+        /// The chain's native currency held by the contract
+        Native,
+        /// A registered PSP22 token contract
+        Psp22(H160),
+    }
+
+    /// Errors returned by PSP22 token contracts
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Psp22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
+    }
+
+    /// What a vote did. A vote arriving after voting ended settles the proposal instead,
+    /// which an error would revert.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum VoteOutcome {
+        Cast,
+        /// Voting had ended; the vote was not recorded and the proposal settled with this status
+        VotingClosed(ProposalStatus),
+    }
+
+    /// What `finalize_batch` did with one of the proposal ids it was given
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum FinalizeOutcome {
+        NotFound,
+        /// Nothing to settle yet; the proposal keeps this status
+        Unchanged(ProposalStatus),
+        /// Finalization moved the proposal to this status
+        Settled(ProposalStatus),
+        /// Its election tally did not fit in this batch's ballot budget; finalize it in another call
+        Deferred,
+    }
+
+    /// Payment made by the treasury when a spend proposal is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct TreasurySpend {
+        pub recipient: SpendRecipient,
+        pub amount: Balance,
+        pub asset: Asset,
+        /// Pot the spend draws from; the unallocated main treasury when unset
+        pub pot: Option<String>,
+    }
+
+    /// Who a treasury spend is paid to
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum SpendRecipient {
+        Single(H160),
+        /// Shares of the amount for several recipients
+        Split(Vec<SpendShare>),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum SharePortion {
+        Amount(Balance),
+        /// Out of 10 000; rounding dust goes to the last recipient
+        BasisPoints(u16),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SpendShare {
+        pub recipient: H160,
+        pub portion: SharePortion,
+    }
+
+    /// Rules a pot applies to the spends drawing from it
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct PotPolicy {
+        pub max_single_spend: Option<Balance>,
+        /// Spends need sign-off from one of the pot's stewards before execution
+        pub require_steward_approval: bool,
+    }
+
+    /// Earmarked share of the native treasury balance
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Pot {
+        pub name: String,
+        pub balance: Balance,
+        /// Approved spends from the pot that have not been paid yet
+        pub liabilities: Balance,
+        pub policy: PotPolicy,
+        pub stewards: Vec<H160>,
+    }
+
+    /// Change to the pots applied when a proposal carrying it is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PotAction {
+        CreatePot { name: String, policy: PotPolicy, stewards: Vec<H160> },
+        UpdatePot { name: String, policy: PotPolicy, stewards: Vec<H160> },
+        /// Move funds between pots; `None` is the unallocated main treasury
+        Transfer { from: Option<String>, to: Option<String>, amount: Balance },
+    }
+
+    /// How an executed spend is paid out over time instead of in one transfer
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PayoutSchedule {
+        /// Vests linearly over `duration` blocks
+        Linear { duration: u32 },
+        /// Vests linearly over `duration` blocks, but nothing is claimable for the first `cliff` blocks
+        Cliff { cliff: u32, duration: u32 },
+    }
+
+    /// Payment stream created when a spend proposal with a payout schedule is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Stream {
+        pub proposal_id: u32,
+        pub beneficiary: H160,
+        pub total: Balance,
+        pub claimed: Balance,
+        pub start_block: u32,
+        pub cliff_block: u32,
+        pub end_block: u32,
+        pub cancelled_at: Option<u32>,
+    }
+
+    /// Caps on treasury outflows authorized by executed proposals
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct OutflowLimits {
+        pub max_single_spend: Option<Balance>,
+        /// Length of the rolling window the cumulative caps apply to
+        pub window_blocks: u32,
+        pub max_window_outflow: Option<Balance>,
+        /// Cumulative cap per rolling window for each listed proposal type
+        pub type_budgets: Vec<(ProposalType, Balance)>,
+        /// Cumulative cap per rolling window for each PSP22 token; unlisted tokens cannot be spent
+        pub token_budgets: Vec<(H160, Balance)>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct OutflowRecord {
+        pub block: u32,
+        pub amount: Balance,
+        pub asset: Asset,
+        pub proposal_type: ProposalType,
+    }
+
+    /// Spend period settings: approved spends are paid in batches at each period boundary
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SpendPeriodConfig {
+        pub period_blocks: u32,
+        /// Percentage of the unspent available balance burned each period
+        pub burn_percent: u32,
+    }
+
+    /// What happened at a spend period boundary
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SpendPeriodReport {
+        pub period: u32,
+        pub processed_at: u32,
+        pub paid_proposals: Vec<u32>,
+        pub total_paid: Balance,
+        pub burned: Balance,
+        pub deferred_proposals: Vec<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Milestone {
+        pub amount: Balance,
+        pub deliverable_hash: Hash,
+    }
+
+    /// Terms of a milestone grant, paid out milestone by milestone after review
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct GrantTerms {
+        pub beneficiary: H160,
+        pub milestones: Vec<Milestone>,
+        pub reviewers: Vec<H160>,
+        pub approvals_required: u32,
+        /// Blocks after execution before undelivered milestones can be returned
+        pub completion_blocks: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum MilestoneStatus {
+        Pending,
+        Submitted,
+        Released,
+        Failed,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct MilestoneState {
+        pub milestone: Milestone,
+        pub status: MilestoneStatus,
+        pub evidence: Option<Hash>,
+        pub approvals: Vec<H160>,
+        pub rejections: Vec<H160>,
+    }
+
+    /// Escrowed grant created when a grant proposal is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Grant {
+        pub proposal_id: u32,
+        pub beneficiary: H160,
+        pub reviewers: Vec<H160>,
+        pub approvals_required: u32,
+        pub milestones: Vec<MilestoneState>,
+        pub deadline: u32,
+    }
+
+    /// Terms of a bounty; its value is escrowed when the funding proposal is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct BountyTerms {
+        pub description: String,
+        pub curator: H160,
+        /// Part of the bounty value paid to the curator once all of it is awarded
+        pub curator_fee: Balance,
+        /// Blocks after execution before unawarded value returns to the treasury
+        pub duration_blocks: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ChildBountyStatus {
+        Open,
+        Awarded,
+        Claimed,
+        Expired,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ChildBounty {
+        pub description: String,
+        pub value: Balance,
+        pub status: ChildBountyStatus,
+        pub beneficiary: Option<H160>,
+        pub awarded_at: Option<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Bounty {
+        pub proposal_id: u32,
+        pub description: String,
+        pub curator: H160,
+        pub curator_fee: Balance,
+        pub fee_paid: bool,
+        /// Value not yet split into child bounties
+        pub unallocated: Balance,
+        pub children: Vec<ChildBounty>,
+        pub expires_at: u32,
+        pub expired: bool,
+    }
+
+    /// Small retroactive reward nominated by a member and sized by the council
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Tip {
+        pub id: u32,
+        pub reason: Hash,
+        pub beneficiary: H160,
+        pub finder: H160,
+        /// Amount each council member has proposed
+        pub tips: Vec<(H160, Balance)>,
+        /// Set once a majority of the council has tipped
+        pub closes_at: Option<u32>,
+        pub paid: Option<Balance>,
+    }
+
+    /// Executed spend waiting for its beneficiary to withdraw it
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct Claim {
+        pub id: u32,
+        pub source: PayoutSource,
+        pub beneficiary: H160,
+        pub amount: Balance,
+        pub asset: Asset,
+        /// Block after which unclaimed funds return to the treasury
+        pub expires_at: u32,
+    }
+
+    /// What a claim pays out. Spends, grants, streams, bounties and deposit refunds
+    /// are identified by their proposal.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum PayoutSource {
+        Proposal(u32),
+        Tip(u32),
+    }
+
+    /// Minimum parameters and deposit for proposals of one type
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ProposalPolicy {
+        pub min_voting_period: VotingPeriod,
+        pub min_quorum: QuorumThreshold,
+        pub min_execution_delay: ExecutionDelay,
+        /// Native deposit sent with the proposal, returned once voting closes with quorum
+        pub deposit: Balance,
+        /// Whether proposals of this type may opt into early decision
+        pub allow_early_decision: bool,
+    }
+
+    /// Endorsement new proposals need before they go to a vote
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct SponsorshipConfig {
+        /// Registered members other than the proposer who must second the proposal
+        pub seconds_required: u32,
+        /// Deposit that sponsors the proposal on its own, if set
+        pub deposit_threshold: Option<Balance>,
+        /// Blocks an un-sponsored proposal waits before it lapses
+        pub lapse_blocks: u32,
+    }
+
+    /// Extension of the voting period when a late vote changes the winning option
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct AntiSnipingConfig {
+        /// Blocks before `voting_end` in which a change of winner triggers an extension
+        pub window_blocks: u32,
+        /// Blocks added to `voting_end` and `execution_time` per extension
+        pub extension_blocks: u32,
+        pub max_extensions: u32,
+    }
+
+    /// Who can register as a voter
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum MembershipMode {
+        /// Anyone can register
+        Open,
+        /// Voters are only admitted by governance
+        Closed,
+    }
+
+    /// Change to the contract's own configuration applied when a proposal carrying it is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum ConfigChange {
+        SetProposalPolicy { proposal_type: ProposalType, policy: Option<ProposalPolicy> },
+        SetMaxVotingOptions(u32),
+        SetMembershipMode(MembershipMode),
+        SetSponsorship(Option<SponsorshipConfig>),
+        SetAntiSniping(Option<AntiSnipingConfig>),
+        /// Blocks a proposal must hold quorum and approval to pass before `voting_end`
+        SetConfirmPeriod(Option<u32>),
+        /// Clock used to schedule proposals created from now on
+        SetClock(SchedulingClock),
+        AdmitVoters(Vec<H160>),
+        SetGuardians(Vec<H160>),
+        SetOutflowLimits(Option<OutflowLimits>),
+        ResumeOutflows,
+        SetSpendPeriodConfig(Option<SpendPeriodConfig>),
+        AddAcceptedAsset(H160),
+        RemoveAcceptedAsset(H160),
+        /// Largest amount a council member may tip
+        SetMaxTip(Balance),
+    }
+
+    /// Change to the council applied when a proposal carrying it is executed
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum CouncilChange {
+        SetMembers(Vec<H160>),
+        AddMember(H160),
+        RemoveMember(H160),
+        SetPrime(Option<H160>),
+        /// Seat the winners of an election whose candidates are these accounts
+        ElectMembers(Vec<H160>),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub enum CouncilMotionKind {
+        /// Block a passed Treasury proposal before its execution time
+        Veto,
+        /// End the voting period of an active proposal and skip its execution delay
+        FastTrack,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct CouncilMotion {
+        pub proposal_id: u32,
+        pub kind: CouncilMotionKind,
+        pub ayes: Vec<H160>,
+        pub nays: Vec<H160>,
+        pub opened_at: u32,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
     pub enum ProposalStatus {
+        /// Waiting for enough seconds or deposit before voting opens
+        Proposed,
+        /// Draft the proposer can still edit; voting opens at `voting_start`
+        Pending,
         Active,
+        /// Meeting quorum and approval, still open for votes until the confirm period elapses
+        Confirming,
         Passed,
         Rejected,
         Executed,
         Expired,
+        RunoffScheduled,
+        Vetoed,
+        /// Executed spend waiting in the approvals queue for the next spend period
+        Queued,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -127,11 +626,39 @@ mod treasury_governance {
         pub voting_options: VotingOptions,
         pub proposer: H160,
         pub created_at: u32,
-        pub voting_end: u32,
-        pub execution_time: u32,
+        /// Measured on the proposal's clock, like `voting_end` and `execution_time`
+        pub voting_start: u64,
+        pub voting_end: u64,
+        pub execution_time: u64,
+        pub clock: SchedulingClock,
         pub status: ProposalStatus,
         pub vote_counts: Vec<u128>,
         pub total_voters: u32,
+        /// Vote ordinal at which each option last received a vote
+        pub last_vote_ordinals: Vec<u32>,
+        pub parent_proposal: Option<u32>,
+        pub runoff_proposal: Option<u32>,
+        /// Number of seats to fill, set only for election proposals
+        pub seats: Option<u32>,
+        pub council_change: Option<CouncilChange>,
+        pub spend: Option<TreasurySpend>,
+        pub grant_terms: Option<GrantTerms>,
+        pub payout_schedule: Option<PayoutSchedule>,
+        /// Stream cancelled, reclaiming its unvested remainder, when this proposal is executed
+        pub cancel_stream: Option<u32>,
+        pub pot_action: Option<PotAction>,
+        pub bounty_terms: Option<BountyTerms>,
+        pub config_changes: Vec<ConfigChange>,
+        /// Code hash the contract is upgraded to when this proposal is executed
+        pub code_upgrade: Option<H256>,
+        /// Time after which the proposal lapses if it is still waiting for sponsorship, on the proposal's clock
+        pub sponsorship_deadline: Option<u64>,
+        /// Clock time of each late vote that extended the voting period
+        pub voting_extensions: Vec<u64>,
+        /// Clock time the proposal entered its current confirm period
+        pub confirm_start: Option<u64>,
+        /// Registered voters when the proposal was created, the only ones who may vote on it with `early_decision`
+        pub electorate: u32,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -144,6 +671,111 @@ mod treasury_governance {
         pub weight: u128,
     }
 
+    /// Proposal status as stored by the first release, before storage was versioned
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode)]
+    enum LegacyProposalStatus {
+        Active,
+        Passed,
+        Rejected,
+        Executed,
+        Expired,
+    }
+
+    /// Governance parameters as stored by the first release, before tie policies
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode)]
+    struct LegacyGovernanceParameters {
+        voting_period: VotingPeriod,
+        quorum_threshold: QuorumThreshold,
+        execution_delay: ExecutionDelay,
+    }
+
+    /// Proposal as stored by the first release, storage version 0
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode)]
+    struct LegacyProposal {
+        id: u32,
+        title: String,
+        description: String,
+        proposal_type: ProposalType,
+        governance_params: LegacyGovernanceParameters,
+        voting_options: VotingOptions,
+        proposer: H160,
+        created_at: u32,
+        voting_end: u32,
+        execution_time: u32,
+        status: LegacyProposalStatus,
+        vote_counts: Vec<u128>,
+        total_voters: u32,
+    }
+
+    /// Approval ballot cast on an election proposal
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ApprovalBallot {
+        pub voter: H160,
+        pub approvals: Vec<u32>,
+        pub timestamp: u32,
+        pub weight: u128,
+    }
+
+    /// Configuration a deployment starts with
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct GenesisConfig {
+        pub owner: H160,
+        pub guardians: Vec<H160>,
+        /// Voters registered at deployment
+        pub members: Vec<H160>,
+        pub proposal_policies: Vec<(ProposalType, ProposalPolicy)>,
+        pub max_voting_options: u32,
+        /// Milliseconds per block on the target chain
+        pub block_time_ms: u32,
+        pub membership_mode: MembershipMode,
+        pub clock: SchedulingClock,
+    }
+
+    /// Contract-wide state carried over by a state export
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct StateExportHeader {
+        /// Format version; imports reject versions they do not understand
+        pub version: u32,
+        pub next_proposal_id: u32,
+        pub proposal_count: u32,
+        pub voter_count: u32,
+        pub total_voters: u32,
+        pub council_members: Vec<H160>,
+        pub council_prime: Option<H160>,
+        pub block_time_ms: u32,
+        pub next_spend_period: u32,
+        pub spend_period_index: u32,
+        pub next_tip_id: u32,
+        /// Settings rebuilt on import through the changes governance applies
+        pub config: Vec<ConfigChange>,
+    }
+
+    /// A proposal with the votes, ballots, seconds and council motions on it, as exported
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct ExportedProposal {
+        pub proposal: Proposal,
+        pub votes: Vec<Vote>,
+        pub approval_ballots: Vec<ApprovalBallot>,
+        pub election_winners: Option<Vec<u32>>,
+        pub seconds: Vec<H160>,
+        pub council_motions: Vec<CouncilMotion>,
+        /// Settled grant, stream or bounty records the proposal funded
+        pub grant: Option<Grant>,
+        pub stream: Option<Stream>,
+        pub bounty: Option<Bounty>,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
@@ -152,6 +784,16 @@ mod treasury_governance {
         pub active_proposals: u32,
         pub executed_proposals: u32,
         pub total_voters: u32,
+        pub pots: Vec<PotSummary>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std", derive(ink::storage::traits::StorageLayout))]
+    pub struct PotSummary {
+        pub name: String,
+        pub balance: Balance,
+        pub liabilities: Balance,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -182,29 +824,278 @@ mod treasury_governance {
         TooManyVotingOptions,
         ArithmeticOverflow,
         NotRegisteredVoter,
+        InvalidSeatCount,
+        EmptyBallot,
+        NotAnElection,
+        ApprovalBallotRequired,
+        NotCouncilMember,
+        NotVetoable,
+        CouncilMotionNotFound,
+        CouncilMotionNotReady,
+        InvalidCouncilChange,
+        InvalidSpend,
+        InsufficientTreasuryBalance,
+        TransferFailed,
+        InvalidGrant,
+        GrantNotFound,
+        InvalidMilestone,
+        GrantStillOpen,
+        InvalidSchedule,
+        StreamNotFound,
+        StreamCancelled,
+        NothingToClaim,
+        InvalidSpendPeriodConfig,
+        SpendPeriodsDisabled,
+        SpendPeriodNotReached,
+        InvalidOutflowLimits,
+        TreasuryPaused,
+        /// Paying the spend now would breach an outflow limit
+        OutflowLimitExceeded,
+        /// The spend fits the outflow limits, so there is no breach to report
+        OutflowWithinLimits,
+        AssetNotAccepted,
+        TokenTransferFailed,
+        PotNotFound,
+        PotAlreadyExists,
+        InsufficientPotBalance,
+        PotPolicyViolation,
+        PotSpendNotApproved,
+        InvalidBounty,
+        BountyNotFound,
+        BountyExpired,
+        BountyStillOpen,
+        ChildBountyNotFound,
+        PayoutNotReady,
+        TipNotFound,
+        TipClosed,
+        TipTooLarge,
+        ClaimNotFound,
+        ClaimExpired,
+        ClaimStillOpen,
+        PolicyViolation,
+        InsufficientDeposit,
+        RegistrationClosed,
+        InvalidConfigChange,
+        UpgradeDelayTooShort,
+        UpgradeFailed,
+        ImportClosed,
+        UnsupportedExportVersion,
+        /// Governance is closed until the owner finishes importing state
+        ImportInProgress,
+        /// Early-decision proposals only take votes from their snapshot electorate
+        RegisteredAfterProposal,
+        /// Funds or payouts are still open, and an export would leave them behind
+        OpenFinancialState,
+        InvalidGenesisConfig,
+        VotingNotStarted,
+        InvalidVotingStart,
+        ProposalNotEditable,
+        NotSponsorable,
+        AlreadySeconded,
+        TooManyDepositContributors,
+        ProposalLapsed,
+        BatchTooLarge,
+        ElectionFull,
+        RunoffNotSupported,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
-    // Events temporarily removed due to ink! v6 alpha bugs
+    /// Fixed-point scale used for voter loads in sequential Phragmén
+    const PHRAGMEN_SCALE: u128 = 1_000_000_000_000;
 
-    // Storage
-    #[ink(storage)]
-    pub struct TreasuryGovernance {
-        pub next_proposal_id: u32,
+    /// Blocks a council motion stays open before abstentions follow the prime member's vote
+    const COUNCIL_MOTION_DURATION: u32 = 24 * 60 * 10;
+
+    /// Blocks between awarding a child bounty and its beneficiary being able to claim it
+    const BOUNTY_PAYOUT_DELAY: u32 = 24 * 60 * 10;
+
+    /// Blocks between a tip reaching its tipper threshold and it being payable
+    const TIP_COUNTDOWN: u32 = 60 * 10;
+
+    /// Maximum number of recipients a split spend can pay
+    const MAX_SPEND_SPLITS: usize = 10;
+
+    /// Version of the code's storage layout, bumped with each release that needs a migration.
+    /// The first release stored no version and is version 0.
+    const CONTRACT_VERSION: u32 = 1;
+
+    /// Block time the contract's durations are expressed in; other chains scale them
+    const DEFAULT_BLOCK_TIME_MS: u32 = 6_000;
+
+    /// Upper bound on the voting options limit a deployment can be configured with
+    const MAX_OPTIONS_LIMIT: u32 = 100;
+
+    /// Maximum number of approval ballots an election accepts, bounding the cost of its tally
+    const MAX_ELECTION_BALLOTS: u32 = 500;
+
+    /// Maximum number of proposals `finalize_batch` settles in one call
+    const MAX_FINALIZE_BATCH: usize = 20;
+
+    /// Maximum number of election ballots `finalize_batch` tallies in one call, the cost of one full election
+    const MAX_FINALIZE_BATCH_BALLOTS: u32 = MAX_ELECTION_BALLOTS;
+
+    /// Version of the SCALE format produced by the state export messages
+    const EXPORT_FORMAT_VERSION: u32 = 1;
+
+    /// Minimum execution delay for code upgrades, giving members time to react
+    const MIN_UPGRADE_DELAY: u32 = 2 * 24 * 60 * 10;
+
+    /// Blocks a beneficiary has to withdraw an executed spend
+    const CLAIM_PERIOD: u32 = 30 * 24 * 60 * 10;
+
+    /// Maximum number of accounts that can contribute to one proposal's deposit
+    const MAX_DEPOSIT_CONTRIBUTORS: usize = 20;
+
+    // Events temporarily removed due to ink! v6 alpha bugs
+
+    // Storage
+    #[ink(storage)]
+    pub struct TreasuryGovernance {
+        pub next_proposal_id: u32,
         pub proposals: Mapping<u32, Proposal>,
         pub votes: Mapping<(u32, H160), Vote>,
         pub proposal_ids: Vec<u32>,
         pub total_voters: u32,
         pub owner: H160,
         pub registered_voters: Mapping<H160, bool>,
+        /// Approval ballots by election and voter, listed in `proposal_voters`
+        pub election_ballots: Mapping<(u32, H160), ApprovalBallot>,
+        pub election_winners: Mapping<u32, Vec<u32>>,
+        pub council_members: Vec<H160>,
+        pub council_prime: Option<H160>,
+        pub council_motions: Mapping<(u32, CouncilMotionKind), CouncilMotion>,
+        /// Total of approved spends that have not been paid yet
+        pub approved_liabilities: Balance,
+        /// Total held in escrow for unreleased grant milestones, streams and bounties
+        pub escrowed_funds: Balance,
+        pub grants: Mapping<u32, Grant>,
+        pub streams: Mapping<u32, Stream>,
+        pub spend_period_config: Option<SpendPeriodConfig>,
+        pub next_spend_period: u32,
+        pub spend_period_index: u32,
+        pub approvals_queue: Vec<u32>,
+        pub spend_reports: Mapping<u32, SpendPeriodReport>,
+        /// Native funds burned at spend-period ends; excluded from the treasury balance
+        pub burned_funds: Balance,
+        pub outflow_limits: Option<OutflowLimits>,
+        /// Set when an execution would have breached the outflow limits
+        pub outflow_paused: bool,
+        pub recent_outflows: Vec<OutflowRecord>,
+        /// PSP22 token contracts the treasury accepts
+        pub accepted_assets: Vec<H160>,
+        /// Approved but unpaid spends per PSP22 token
+        pub asset_liabilities: Mapping<H160, Balance>,
+        pub pots: Mapping<String, Pot>,
+        pub pot_names: Vec<String>,
+        /// Sum of all pot balances, excluded from the main treasury's available balance
+        pub pots_total: Balance,
+        /// Steward who signed off each pot spend
+        pub pot_spend_approvals: Mapping<u32, H160>,
+        pub bounties: Mapping<u32, Bounty>,
+        pub tips: Mapping<u32, Tip>,
+        pub next_tip_id: u32,
+        /// Tips reported but not yet paid
+        pub open_tips: u32,
+        /// Largest amount a council member may tip; zero until governance sets a cap
+        pub max_tip: Balance,
+        pub claims: Mapping<u32, Claim>,
+        pub next_claim_id: u32,
+        /// Open claim ids per beneficiary
+        pub beneficiary_claims: Mapping<H160, Vec<u32>>,
+        pub proposal_policies: Vec<(ProposalType, ProposalPolicy)>,
+        pub max_voting_options: u32,
+        pub membership_mode: MembershipMode,
+        /// Accounts allowed to trip the outflow circuit breaker
+        pub guardians: Vec<H160>,
+        /// Deposits held for proposals still being voted on
+        pub proposal_deposits: Mapping<u32, Balance>,
+        pub held_deposits: Balance,
+        /// Storage layout version, brought up to `CONTRACT_VERSION` by `migrate`
+        pub storage_version: u32,
+        /// Every registered voter, indexed by registration order
+        pub voter_list: Mapping<u32, H160>,
+        pub voter_list_len: u32,
+        /// Position of each voter in `voter_list`
+        pub voter_positions: Mapping<H160, u32>,
+        /// Voters on each proposal, indexed by the order their votes were cast
+        pub proposal_voters: Mapping<(u32, u32), H160>,
+        /// Set while a deployment created by `new_for_import` is being filled
+        pub importing: bool,
+        pub block_time_ms: u32,
+        pub clock: SchedulingClock,
+        pub sponsorship: Option<SponsorshipConfig>,
+        /// Members who seconded each proposal
+        pub proposal_seconds: Mapping<u32, Vec<H160>>,
+        pub anti_sniping: Option<AntiSnipingConfig>,
+        pub confirm_period: Option<u32>,
+        /// Share of each proposal's deposit put up by each account
+        pub deposit_contributions: Mapping<(u32, H160), Balance>,
+        /// Accounts that contributed to each proposal's deposit
+        pub deposit_contributors: Mapping<u32, Vec<H160>>,
     }
 
     impl TreasuryGovernance {
         /// Constructor that initializes the contract
         #[ink(constructor)]
         pub fn new() -> Self {
-            let caller = Self::env().caller();
+            Self::initial_state(Self::env().caller())
+        }
+
+        /// Constructor for a fresh deployment that the owner fills from a state export
+        #[ink(constructor)]
+        pub fn new_for_import() -> Self {
+            let mut contract = Self::initial_state(Self::env().caller());
+            contract.importing = true;
+            contract
+        }
+
+        /// Constructor that starts the contract from a genesis configuration
+        #[ink(constructor)]
+        pub fn new_with_config(config: GenesisConfig) -> Result<Self> {
+            if config.max_voting_options < 2
+                || config.max_voting_options > MAX_OPTIONS_LIMIT
+                || config.block_time_ms == 0
+                || config.owner == H160::zero()
+            {
+                return Err(Error::InvalidGenesisConfig);
+            }
+            // A closed deployment without members could never vote anyone in
+            if config.membership_mode == MembershipMode::Closed && config.members.is_empty() {
+                return Err(Error::InvalidGenesisConfig);
+            }
+            if !Self::is_valid_account_list(&config.members) || !Self::is_valid_account_list(&config.guardians) {
+                return Err(Error::InvalidGenesisConfig);
+            }
+            for (index, (proposal_type, _)) in config.proposal_policies.iter().enumerate() {
+                if config.proposal_policies[..index].iter().any(|(existing, _)| existing == proposal_type) {
+                    return Err(Error::InvalidGenesisConfig);
+                }
+            }
+            
+            let mut contract = Self::initial_state(config.owner);
+            for member in config.members {
+                contract.add_voter(member)?;
+            }
+            contract.guardians = config.guardians;
+            contract.proposal_policies = config.proposal_policies;
+            contract.max_voting_options = config.max_voting_options;
+            contract.block_time_ms = config.block_time_ms;
+            contract.membership_mode = config.membership_mode;
+            contract.clock = config.clock;
+            
+            Ok(contract)
+        }
+
+        /// Internal helper checking a member or guardian list has no duplicates or zero address
+        fn is_valid_account_list(accounts: &[H160]) -> bool {
+            accounts.iter().enumerate().all(|(index, account)| {
+                *account != H160::zero() && !accounts[..index].contains(account)
+            })
+        }
+
+        /// Internal helper building the storage of a new deployment
+        fn initial_state(caller: H160) -> Self {
             Self {
                 next_proposal_id: 1,
                 proposals: Default::default(),
@@ -213,31 +1104,104 @@ mod treasury_governance {
                 total_voters: 0,
                 owner: caller,
                 registered_voters: Default::default(),
+                election_ballots: Default::default(),
+                election_winners: Default::default(),
+                council_members: Vec::new(),
+                council_prime: None,
+                council_motions: Default::default(),
+                approved_liabilities: 0,
+                escrowed_funds: 0,
+                grants: Default::default(),
+                streams: Default::default(),
+                spend_period_config: None,
+                next_spend_period: 0,
+                spend_period_index: 0,
+                approvals_queue: Vec::new(),
+                spend_reports: Default::default(),
+                burned_funds: 0,
+                outflow_limits: None,
+                outflow_paused: false,
+                recent_outflows: Vec::new(),
+                accepted_assets: Vec::new(),
+                asset_liabilities: Default::default(),
+                pots: Default::default(),
+                pot_names: Vec::new(),
+                pots_total: 0,
+                pot_spend_approvals: Default::default(),
+                bounties: Default::default(),
+                tips: Default::default(),
+                next_tip_id: 0,
+                open_tips: 0,
+                max_tip: 0,
+                claims: Default::default(),
+                next_claim_id: 0,
+                beneficiary_claims: Default::default(),
+                proposal_policies: Vec::new(),
+                max_voting_options: 10,
+                membership_mode: MembershipMode::Open,
+                guardians: Vec::new(),
+                proposal_deposits: Default::default(),
+                held_deposits: 0,
+                storage_version: CONTRACT_VERSION,
+                voter_list: Default::default(),
+                voter_list_len: 0,
+                voter_positions: Default::default(),
+                proposal_voters: Default::default(),
+                importing: false,
+                block_time_ms: DEFAULT_BLOCK_TIME_MS,
+                clock: SchedulingClock::BlockNumber,
+                sponsorship: None,
+                anti_sniping: None,
+                confirm_period: None,
+                proposal_seconds: Default::default(),
+                deposit_contributions: Default::default(),
+                deposit_contributors: Default::default(),
             }
         }
 
         /// Register a voter to participate in governance
         #[ink(message)]
         pub fn register_voter(&mut self) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_not_importing()?;
             let caller = self.env().caller();
             
             // Check if already registered
-            if self.registered_voters.get(&caller).unwrap_or(false) {
+            if self.registered_voters.get(caller).unwrap_or(false) {
                 return Ok(()); // Already registered, no error
             }
+            if self.membership_mode == MembershipMode::Closed {
+                return Err(Error::RegistrationClosed);
+            }
             
             // Register the voter
-            self.registered_voters.insert(&caller, &true);
-            self.total_voters = self.total_voters.checked_add(1)
-                .ok_or(Error::ArithmeticOverflow)?;
+            self.add_voter(caller)?;
             
             // Event emission removed due to ink! v6 alpha bugs
             
             Ok(())
         }
 
+        /// Internal helper registering a new voter and appending them to the voter list
+        fn add_voter(&mut self, voter: H160) -> Result<()> {
+            self.list_voter(voter)?;
+            self.total_voters = self.total_voters.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Internal helper marking an account as registered and appending it to the voter list
+        fn list_voter(&mut self, voter: H160) -> Result<()> {
+            self.registered_voters.insert(voter, &true);
+            self.voter_list.insert(self.voter_list_len, &voter);
+            self.voter_positions.insert(voter, &self.voter_list_len);
+            self.voter_list_len = self.voter_list_len.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
         /// Create a new proposal
-        #[ink(message)]
+        #[ink(message, payable)]
         pub fn create_proposal(
             &mut self,
             title: String,
@@ -246,890 +1210,7166 @@ mod treasury_governance {
             governance_params: GovernanceParameters,
             voting_options: VotingOptions,
         ) -> Result<u32> {
+            self.migrate_storage()?;
             let caller = self.env().caller();
-            let current_block = self.env().block_number();
             
-            // Validate voting options
-            if voting_options.options.is_empty() {
-                return Err(Error::NoVotingOptions);
+            // Elections carry a seat count and must go through create_election
+            if proposal_type == ProposalType::Election {
+                return Err(Error::InvalidProposal);
             }
-            if voting_options.options.len() > 10 {
-                return Err(Error::TooManyVotingOptions);
-            }
-            
-            // Calculate timing
-            let voting_end = current_block.checked_add(governance_params.voting_period.to_blocks())
-                .ok_or(Error::ArithmeticOverflow)?;
-            let execution_time = voting_end.checked_add(governance_params.execution_delay.to_blocks())
-                .ok_or(Error::ArithmeticOverflow)?;
             
-            // Initialize vote counts
-            let vote_counts = vec![0u128; voting_options.options.len()];
+            // Validate voting options
+            self.validate_voting_options(&voting_options)?;
             
-            // Create proposal
-            let proposal_id = self.next_proposal_id;
-            let proposal = Proposal {
-                id: proposal_id,
-                title: title.clone(),
+            let proposal = self.build_proposal(
+                title,
                 description,
                 proposal_type,
                 governance_params,
                 voting_options,
-                proposer: caller,
-                created_at: current_block,
-                voting_end,
-                execution_time,
-                status: ProposalStatus::Active,
-                vote_counts,
-                total_voters: 0,
-            };
-            
-            // Store proposal
-            self.proposals.insert(&proposal_id, &proposal);
-            self.proposal_ids.push(proposal_id);
-            self.next_proposal_id = self.next_proposal_id.checked_add(1)
-                .ok_or(Error::ArithmeticOverflow)?;
+                caller,
+            )?;
+            let proposal_id = self.store_new_proposal(&proposal)?;
             
             // Event emission removed due to ink! v6 alpha bugs
             
             Ok(proposal_id)
         }
 
-        /// Cast a vote on a proposal
-        #[ink(message)]
-        pub fn vote(&mut self, proposal_id: u32, option_index: u32) -> Result<()> {
+        /// Create a draft proposal that opens for voting at `voting_start`, measured on the
+        /// contract's scheduling clock. Until then the proposer can edit it.
+        #[ink(message, payable)]
+        pub fn create_draft_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            proposal_type: ProposalType,
+            governance_params: GovernanceParameters,
+            voting_options: VotingOptions,
+            voting_start: u64,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
             let caller = self.env().caller();
-            let current_block = self.env().block_number();
-            
-            // Check if voter is registered
-            if !self.registered_voters.get(&caller).unwrap_or(false) {
-                return Err(Error::NotRegisteredVoter);
-            }
-            
-            // Get proposal
-            let mut proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
-            
-            // Validate proposal status and timing
-            if proposal.status != ProposalStatus::Active {
-                return Err(Error::ProposalNotActive);
-            }
-            if current_block > proposal.voting_end {
-                return Err(Error::VotingPeriodEnded);
-            }
             
-            // Check if user already voted
-            if self.votes.get(&(proposal_id, caller)).is_some() {
-                return Err(Error::AlreadyVoted);
+            if proposal_type == ProposalType::Election {
+                return Err(Error::InvalidProposal);
             }
+            self.validate_voting_options(&voting_options)?;
             
-            // Validate option index
-            if option_index as usize >= proposal.voting_options.options.len() {
-                return Err(Error::InvalidOptionIndex);
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                proposal_type,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            if voting_start <= proposal.voting_start {
+                return Err(Error::InvalidVotingStart);
             }
             
-            // Create vote record
-            let option_text = proposal.voting_options.options[option_index as usize].clone();
-            let vote = Vote {
-                voter: caller,
-                choice: VoteChoice {
-                    option_index,
-                    option_text: option_text.clone(),
-                },
-                timestamp: current_block,
-                weight: 1, // Simple voting weight of 1 for now
-            };
-            
-            // Update vote counts
-            proposal.vote_counts[option_index as usize] = proposal.vote_counts[option_index as usize]
-                .checked_add(vote.weight)
+            // Shift the whole schedule to start at the requested time
+            let offset = voting_start - proposal.voting_start;
+            proposal.voting_start = voting_start;
+            proposal.voting_end = proposal.voting_end.checked_add(offset)
                 .ok_or(Error::ArithmeticOverflow)?;
-            proposal.total_voters = proposal.total_voters.checked_add(1)
+            proposal.execution_time = proposal.execution_time.checked_add(offset)
                 .ok_or(Error::ArithmeticOverflow)?;
-            
-            // Store vote and updated proposal
-            self.votes.insert(&(proposal_id, caller), &vote);
-            self.proposals.insert(&proposal_id, &proposal);
+            proposal.status = ProposalStatus::Pending;
+            let proposal_id = self.store_new_proposal(&proposal)?;
             
             // Event emission removed due to ink! v6 alpha bugs
             
-            Ok(())
+            Ok(proposal_id)
         }
 
-        /// Update proposal status based on voting results
+        /// Edit a draft proposal before its voting opens (proposer only)
         #[ink(message)]
-        pub fn update_proposal_status(&mut self, proposal_id: u32) -> Result<()> {
-            let current_block = self.env().block_number();
-            
-            let mut proposal = self.proposals.get(&proposal_id)
+        pub fn edit_proposal(
+            &mut self,
+            proposal_id: u32,
+            title: String,
+            description: String,
+            voting_options: VotingOptions,
+        ) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_not_importing()?;
+            let mut proposal = self.proposals.get(proposal_id)
                 .ok_or(Error::ProposalNotFound)?;
-            
-            // Only update if currently active and voting period has ended
-            if proposal.status != ProposalStatus::Active || current_block <= proposal.voting_end {
-                return Ok(());
+            if self.env().caller() != proposal.proposer {
+                return Err(Error::NotAuthorized);
             }
+            if proposal.status != ProposalStatus::Pending
+                || self.clock_now(&proposal.clock) >= proposal.voting_start
+            {
+                return Err(Error::ProposalNotEditable);
+            }
+            self.validate_voting_options(&voting_options)?;
             
-            let _old_status = proposal.status.clone();
+            proposal.vote_counts = vec![0u128; voting_options.options.len()];
+            proposal.last_vote_ordinals = vec![0u32; voting_options.options.len()];
+            proposal.title = title;
+            proposal.description = description;
+            proposal.voting_options = voting_options;
+            self.proposals.insert(proposal_id, &proposal);
             
-            // Check if quorum is reached
-            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
+            Ok(())
+        }
+
+        /// Internal helper opening a draft for voting once its voting start has passed.
+        /// Returns whether the proposal changed.
+        fn open_voting_if_started(&self, proposal: &mut Proposal) -> bool {
+            if proposal.status == ProposalStatus::Pending
+                && self.clock_now(&proposal.clock) >= proposal.voting_start
+            {
+                proposal.status = ProposalStatus::Active;
+                return true;
+            }
+            false
+        }
+
+        /// Create a multi-seat election where each candidate is a voting option
+        #[ink(message, payable)]
+        pub fn create_election(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            candidates: VotingOptions,
+            seats: u32,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
             
-            if quorum_reached {
-                // Find winning option (highest vote count)
-                let mut max_votes = 0u128;
-                let mut winning_options = Vec::new();
-                
-                for (index, &votes) in proposal.vote_counts.iter().enumerate() {
-                    if votes > max_votes {
-                        max_votes = votes;
-                        winning_options.clear();
-                        winning_options.push(index);
-                    } else if votes == max_votes && votes > 0 {
-                        winning_options.push(index);
-                    }
-                }
-                
-                // If there's a clear winner, mark as passed; otherwise rejected due to tie
-                if winning_options.len() == 1 && max_votes > 0 {
-                    proposal.status = ProposalStatus::Passed;
-                } else {
-                    proposal.status = ProposalStatus::Rejected;
-                }
-            } else {
-                proposal.status = ProposalStatus::Rejected;
+            // Validate candidates and seat count
+            self.validate_voting_options(&candidates)?;
+            if seats == 0 || seats as usize > candidates.options.len() {
+                return Err(Error::InvalidSeatCount);
+            }
+            // Seats are filled by sequential Phragmén over every ballot, which cannot be settled early
+            if governance_params.early_decision {
+                return Err(Error::InvalidProposal);
             }
             
-            // Store updated proposal
-            self.proposals.insert(&proposal_id, &proposal);
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Election,
+                governance_params,
+                candidates,
+                caller,
+            )?;
+            proposal.seats = Some(seats);
+            let proposal_id = self.store_new_proposal(&proposal)?;
             
             // Event emission removed due to ink! v6 alpha bugs
             
+            Ok(proposal_id)
+        }
+
+        /// Deposit native funds into the treasury
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            // The transferred value is credited to the contract's balance
             Ok(())
         }
 
-        /// Execute a passed proposal
-        #[ink(message)]
-        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<()> {
-            let current_block = self.env().block_number();
-            
-            let mut proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
+        /// Create a Treasury proposal that pays `spend` if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_spend_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            spend: TreasurySpend,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
             
-            // Validate proposal can be executed
-            if proposal.status != ProposalStatus::Passed {
-                return Err(Error::ProposalNotReadyForExecution);
+            // Validate the spend against funds not already promised to other proposals
+            if spend.amount == 0 {
+                return Err(Error::InvalidSpend);
             }
-            if current_block < proposal.execution_time {
-                return Err(Error::ProposalNotReadyForExecution);
+            Self::split_spend(&spend)?;
+            if let Asset::Psp22(token) = spend.asset {
+                if !self.accepted_assets.contains(&token) {
+                    return Err(Error::AssetNotAccepted);
+                }
+            }
+            if let Some(name) = &spend.pot {
+                // Pots only hold the native currency
+                if spend.asset != Asset::Native {
+                    return Err(Error::InvalidSpend);
+                }
+                let pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                if pot.policy.max_single_spend.is_some_and(|max| spend.amount > max) {
+                    return Err(Error::PotPolicyViolation);
+                }
             }
+            self.ensure_spend_affordable(&spend)?;
             
-            // Update status to executed
-            proposal.status = ProposalStatus::Executed;
-            self.proposals.insert(&proposal_id, &proposal);
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.spend = Some(spend);
+            let proposal_id = self.store_new_proposal(&proposal)?;
             
             // Event emission removed due to ink! v6 alpha bugs
             
-            Ok(())
+            Ok(proposal_id)
         }
 
-        /// Internal helper to check if quorum is reached
-        fn has_reached_quorum_internal(&self, proposal: &Proposal) -> Result<bool> {
-            if self.total_voters == 0 {
-                return Ok(false);
+        /// Create a Treasury proposal that streams `spend` to its beneficiary if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_stream_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            spend: TreasurySpend,
+            schedule: PayoutSchedule,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let valid_schedule = match schedule {
+                PayoutSchedule::Linear { duration } => duration > 0,
+                PayoutSchedule::Cliff { cliff, duration } => duration > 0 && cliff <= duration,
+            };
+            if !valid_schedule {
+                return Err(Error::InvalidSchedule);
+            }
+            // Streams are escrowed and paid in the native currency to a single beneficiary
+            if spend.asset != Asset::Native || !matches!(spend.recipient, SpendRecipient::Single(_)) {
+                return Err(Error::InvalidSpend);
             }
             
-            let quorum_percentage = proposal.governance_params.quorum_threshold.to_percentage();
-            let required_votes = (self.total_voters as u128 * quorum_percentage as u128) / 100;
-            let total_votes: u128 = proposal.vote_counts.iter().sum();
+            let proposal_id = self.create_spend_proposal(title, description, governance_params, spend)?;
             
-            Ok(total_votes >= required_votes)
-        }
-
-        // Query functions
-
-        /// Get a specific proposal by ID
-        #[ink(message)]
-        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
-            self.proposals.get(&proposal_id)
-        }
-
-        /// Get all proposal IDs
-        #[ink(message)]
-        pub fn get_all_proposal_ids(&self) -> Vec<u32> {
-            self.proposal_ids.clone()
-        }
-
-        /// Get user's vote on a specific proposal
-        #[ink(message)]
-        pub fn get_user_vote(&self, proposal_id: u32, user: H160) -> Option<Vote> {
-            self.votes.get(&(proposal_id, user))
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            proposal.payout_schedule = Some(schedule);
+            self.proposals.insert(proposal_id, &proposal);
+            
+            Ok(proposal_id)
         }
 
-        /// Get contract statistics
-        #[ink(message)]
-        pub fn get_stats(&self) -> ContractStats {
-            let mut active_proposals = 0u32;
-            let mut executed_proposals = 0u32;
+        /// Create a Governance proposal that cancels a stream if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_stream_cancellation(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            stream_id: u32,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
             
-            for proposal_id in &self.proposal_ids {
-                if let Some(proposal) = self.proposals.get(proposal_id) {
-                    match proposal.status {
-                        ProposalStatus::Active => active_proposals += 1,
-                        ProposalStatus::Executed => executed_proposals += 1,
-                        _ => {}
-                    }
-                }
+            let stream = self.streams.get(stream_id)
+                .ok_or(Error::StreamNotFound)?;
+            if stream.cancelled_at.is_some() {
+                return Err(Error::StreamCancelled);
             }
             
-            ContractStats {
-                total_proposals: self.proposal_ids.len() as u32,
-                active_proposals,
-                executed_proposals,
-                total_voters: self.total_voters,
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.cancel_stream = Some(stream_id);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
+        }
+
+        /// Create a Treasury proposal that escrows a milestone grant if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_grant_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            terms: GrantTerms,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            // Validate milestones and the reviewer set
+            if terms.milestones.is_empty()
+                || terms.milestones.len() > 10
+                || terms.milestones.iter().any(|milestone| milestone.amount == 0)
+                || terms.approvals_required == 0
+                || terms.approvals_required as usize > terms.reviewers.len()
+            {
+                return Err(Error::InvalidGrant);
+            }
+            let mut total: Balance = 0;
+            for milestone in &terms.milestones {
+                total = total.checked_add(milestone.amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            if total > self.get_available_balance() {
+                return Err(Error::InsufficientTreasuryBalance);
             }
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            // The grant total is reserved like any other spend until it is escrowed
+            proposal.spend = Some(TreasurySpend {
+                recipient: SpendRecipient::Single(terms.beneficiary),
+                amount: total,
+                asset: Asset::Native,
+                pot: None,
+            });
+            proposal.grant_terms = Some(terms);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
         }
 
-        /// Get total number of registered voters
-        #[ink(message)]
-        pub fn get_total_voters(&self) -> u32 {
-            self.total_voters
+        /// Create a Treasury proposal funding a bounty managed by a curator
+        #[ink(message, payable)]
+        pub fn create_bounty_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            amount: Balance,
+            terms: BountyTerms,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            if amount == 0 || terms.curator_fee >= amount || terms.duration_blocks == 0 {
+                return Err(Error::InvalidBounty);
+            }
+            if amount > self.get_available_balance() {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            // The bounty value is reserved like any other spend until it is escrowed
+            proposal.spend = Some(TreasurySpend {
+                recipient: SpendRecipient::Single(terms.curator),
+                amount,
+                asset: Asset::Native,
+                pot: None,
+            });
+            proposal.bounty_terms = Some(terms);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
         }
 
-        /// Check if a proposal has reached quorum
-        #[ink(message)]
-        pub fn has_reached_quorum(&self, proposal_id: u32) -> Result<bool> {
-            let proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
-            self.has_reached_quorum_internal(&proposal)
+        /// Create a Governance proposal that changes the contract's own configuration if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_config_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            changes: Vec<ConfigChange>,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            if changes.is_empty() || changes.len() > 10 {
+                return Err(Error::InvalidConfigChange);
+            }
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.config_changes = changes;
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
         }
 
-        /// Get proposal results with vote counts
-        #[ink(message)]
-        pub fn get_proposal_results(&self, proposal_id: u32) -> Result<(Vec<u128>, bool)> {
-            let proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
-            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
-            Ok((proposal.vote_counts, quorum_reached))
+        /// Create a Technical proposal that upgrades the contract's code if "Aye" wins.
+        /// Upgrades must wait at least `MIN_UPGRADE_DELAY` blocks after voting ends, and
+        /// `migrate` must be called once the upgrade has executed.
+        #[ink(message, payable)]
+        pub fn create_upgrade_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            code_hash: H256,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            if governance_params.execution_delay.to_blocks() < MIN_UPGRADE_DELAY {
+                return Err(Error::UpgradeDelayTooShort);
+            }
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Technical,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.code_upgrade = Some(code_hash);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
         }
 
-        /// Get voting options for a proposal
-        #[ink(message)]
-        pub fn get_voting_options(&self, proposal_id: u32) -> Result<Vec<String>> {
-            let proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
-            Ok(proposal.voting_options.options)
+        /// Create a Governance proposal that creates, updates or funds a pot if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_pot_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            action: PotAction,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.pot_action = Some(action);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
         }
 
-        /// Get detailed results with option names
-        #[ink(message)]
-        pub fn get_detailed_results(&self, proposal_id: u32) -> Result<ProposalResults> {
-            let proposal = self.proposals.get(&proposal_id)
+        /// Create a Governance proposal that changes the council if "Aye" wins
+        #[ink(message, payable)]
+        pub fn create_council_proposal(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            change: CouncilChange,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            
+            // Council elections go through create_council_election
+            if matches!(change, CouncilChange::ElectMembers(_)) {
+                return Err(Error::InvalidCouncilChange);
+            }
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Aye"), String::from("Nay")],
+            };
+            let mut proposal = self.build_proposal(
+                title,
+                description,
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+                caller,
+            )?;
+            proposal.council_change = Some(change);
+            let proposal_id = self.store_new_proposal(&proposal)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(proposal_id)
+        }
+
+        /// Create an election whose winners replace the council once executed
+        #[ink(message, payable)]
+        pub fn create_council_election(
+            &mut self,
+            title: String,
+            description: String,
+            governance_params: GovernanceParameters,
+            candidates: Vec<H160>,
+            seats: u32,
+        ) -> Result<u32> {
+            self.migrate_storage()?;
+            let candidate_options = VotingOptions {
+                options: candidates.iter().map(|candidate| format!("{:?}", candidate)).collect(),
+            };
+            let proposal_id = self.create_election(
+                title,
+                description,
+                governance_params,
+                candidate_options,
+                seats,
+            )?;
+            
+            let mut proposal = self.proposals.get(proposal_id)
                 .ok_or(Error::ProposalNotFound)?;
-            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
-            let total_votes: u128 = proposal.vote_counts.iter().sum();
+            proposal.council_change = Some(CouncilChange::ElectMembers(candidates));
+            self.proposals.insert(proposal_id, &proposal);
             
-            // Find winning option
-            let mut winning_option = None;
-            let mut max_votes = 0u128;
-            for (index, &votes) in proposal.vote_counts.iter().enumerate() {
-                if votes > max_votes {
-                    max_votes = votes;
-                    winning_option = Some((
-                        index as u32,
-                        proposal.voting_options.options[index].clone(),
-                        votes,
-                    ));
-                }
+            Ok(proposal_id)
+        }
+
+        /// Internal helper to validate the number of voting options
+        fn validate_voting_options(&self, voting_options: &VotingOptions) -> Result<()> {
+            if voting_options.options.is_empty() {
+                return Err(Error::NoVotingOptions);
+            }
+            if voting_options.options.len() > self.max_voting_options as usize {
+                return Err(Error::TooManyVotingOptions);
+            }
+            Ok(())
+        }
+
+        /// Internal helper converting a duration given in 6-second blocks to the chain's block time
+        fn scaled_blocks(&self, blocks: u32) -> u32 {
+            if self.block_time_ms == DEFAULT_BLOCK_TIME_MS {
+                return blocks;
+            }
+            let scaled = u64::from(blocks) * u64::from(DEFAULT_BLOCK_TIME_MS) / u64::from(self.block_time_ms);
+            u32::try_from(scaled).unwrap_or(u32::MAX)
+        }
+
+        /// Internal helper reading the current time on a scheduling clock
+        fn clock_now(&self, clock: &SchedulingClock) -> u64 {
+            match clock {
+                SchedulingClock::BlockNumber => u64::from(self.env().block_number()),
+                SchedulingClock::Timestamp => self.env().block_timestamp(),
             }
+        }
+
+        /// Internal helper computing voting start, voting end and execution time for voting opening now
+        fn voting_schedule(&self, governance_params: &GovernanceParameters, clock: &SchedulingClock) -> Result<(u64, u64, u64)> {
+            let (voting_period, execution_delay) = match clock {
+                SchedulingClock::BlockNumber => (
+                    u64::from(self.scaled_blocks(governance_params.voting_period.to_blocks())),
+                    u64::from(self.scaled_blocks(governance_params.execution_delay.to_blocks())),
+                ),
+                SchedulingClock::Timestamp => (
+                    governance_params.voting_period.to_millis(),
+                    governance_params.execution_delay.to_millis(),
+                ),
+            };
+            let voting_start = self.clock_now(clock);
+            let voting_end = voting_start.checked_add(voting_period)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let execution_time = voting_end.checked_add(execution_delay)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok((voting_start, voting_end, execution_time))
+        }
+
+        /// Internal helper to build a new active proposal under the next proposal ID
+        fn build_proposal(
+            &self,
+            title: String,
+            description: String,
+            proposal_type: ProposalType,
+            governance_params: GovernanceParameters,
+            voting_options: VotingOptions,
+            proposer: H160,
+        ) -> Result<Proposal> {
+            self.ensure_not_importing()?;
+            let current_block = self.env().block_number();
             
-            Ok(ProposalResults {
-                proposal_id,
-                vote_counts: proposal.vote_counts,
-                option_names: proposal.voting_options.options,
-                total_votes,
-                quorum_reached,
-                winning_option,
+            // Calculate timing
+            let (voting_start, voting_end, execution_time) = self.voting_schedule(&governance_params, &self.clock)?;
+            
+            // Initialize vote counts
+            let vote_counts = vec![0u128; voting_options.options.len()];
+            let last_vote_ordinals = vec![0u32; voting_options.options.len()];
+            
+            Ok(Proposal {
+                id: self.next_proposal_id,
+                title,
+                description,
+                proposal_type,
+                governance_params,
+                voting_options,
+                proposer,
+                created_at: current_block,
+                voting_start,
+                voting_end,
+                execution_time,
+                clock: self.clock.clone(),
+                status: ProposalStatus::Active,
+                vote_counts,
+                total_voters: 0,
+                last_vote_ordinals,
+                parent_proposal: None,
+                runoff_proposal: None,
+                seats: None,
+                council_change: None,
+                spend: None,
+                grant_terms: None,
+                payout_schedule: None,
+                cancel_stream: None,
+                pot_action: None,
+                bounty_terms: None,
+                config_changes: Vec::new(),
+                code_upgrade: None,
+                sponsorship_deadline: None,
+                voting_extensions: Vec::new(),
+                confirm_start: None,
+                electorate: self.total_voters,
             })
         }
 
-        /// Get the winning option for a proposal
-        #[ink(message)]
-        pub fn get_winning_option(&self, proposal_id: u32) -> Result<Option<(u32, String, u128)>> {
-            let proposal = self.proposals.get(&proposal_id)
-                .ok_or(Error::ProposalNotFound)?;
+        /// Internal helper to store a freshly built proposal and advance the proposal ID
+        fn store_new_proposal(&mut self, proposal: &Proposal) -> Result<u32> {
+            let proposal_id = proposal.id;
+            let mut proposal = proposal.clone();
             
-            let mut max_votes = 0u128;
-            let mut winning_option = None;
-            let mut tie_exists = false;
+            // A runoff only carries options, so it could not carry out an action on the tied proposal's behalf
+            if Self::carries_action(&proposal) && proposal.governance_params.tie_policy == TiePolicy::Runoff {
+                return Err(Error::RunoffNotSupported);
+            }
             
-            for (index, &votes) in proposal.vote_counts.iter().enumerate() {
-                if votes > max_votes {
-                    max_votes = votes;
-                    winning_option = Some((
-                        index as u32,
-                        proposal.voting_options.options[index].clone(),
-                        votes,
-                    ));
-                    tie_exists = false;
-                } else if votes == max_votes && votes > 0 {
-                    tie_exists = true;
+            // Runoffs inherit the standing of the proposal they settle
+            if proposal.parent_proposal.is_none() {
+                self.enforce_proposal_policy(&proposal)?;
+                
+                // Proposals and drafts wait for sponsorship before they can open
+                if let Some(config) = self.sponsorship.clone() {
+                    if matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Pending)
+                        && !self.deposit_sponsors(proposal_id, &config)
+                    {
+                        proposal.status = ProposalStatus::Proposed;
+                        let lapse_span = self.clock_span(&proposal.clock, config.lapse_blocks);
+                        proposal.sponsorship_deadline = Some(self.clock_now(&proposal.clock).checked_add(lapse_span)
+                            .ok_or(Error::ArithmeticOverflow)?);
+                    }
                 }
             }
+            self.proposals.insert(proposal_id, &proposal);
+            self.proposal_ids.push(proposal_id);
+            self.next_proposal_id = self.next_proposal_id.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
             
-            // Return None if there's a tie or no votes
-            if tie_exists || max_votes == 0 {
-                Ok(None)
-            } else {
-                Ok(winning_option)
-            }
+            Ok(proposal_id)
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::prelude::vec;
+        // Sponsorship
 
-        #[ink::test]
-        fn test_constructor() {
-            let contract = TreasuryGovernance::new();
-            assert_eq!(contract.next_proposal_id, 1);
-            assert_eq!(contract.total_voters, 0);
-            assert_eq!(contract.proposal_ids.len(), 0);
+        /// Second a proposal waiting for sponsorship (registered voters other than the proposer)
+        #[ink(message)]
+        pub fn second_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            if !self.registered_voters.get(caller).unwrap_or(false) {
+                return Err(Error::NotRegisteredVoter);
+            }
+            
+            let mut proposal = self.waiting_for_sponsorship(proposal_id)?;
+            if caller == proposal.proposer {
+                return Err(Error::NotAuthorized);
+            }
+            let mut seconds = self.proposal_seconds.get(proposal_id).unwrap_or_default();
+            if seconds.contains(&caller) {
+                return Err(Error::AlreadySeconded);
+            }
+            
+            seconds.push(caller);
+            self.proposal_seconds.insert(proposal_id, &seconds);
+            let required = self.sponsorship.as_ref().map_or(0, |config| config.seconds_required);
+            if seconds.len() as u32 >= required {
+                self.open_sponsored(&mut proposal)?;
+            }
+            self.proposals.insert(proposal_id, &proposal);
+            
+            Ok(())
+        }
+
+        /// Add to a waiting proposal's deposit; reaching the deposit threshold sponsors it
+        #[ink(message, payable)]
+        pub fn top_up_deposit(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let mut proposal = self.waiting_for_sponsorship(proposal_id)?;
+            let amount = Balance::try_from(self.env().transferred_value())
+                .map_err(|_| Error::ArithmeticOverflow)?;
+            self.hold_deposit(proposal_id, self.env().caller(), amount)?;
+            
+            if let Some(config) = self.sponsorship.clone() {
+                if self.deposit_sponsors(proposal_id, &config) {
+                    self.open_sponsored(&mut proposal)?;
+                    self.proposals.insert(proposal_id, &proposal);
+                }
+            }
+            Ok(())
+        }
+
+        /// Internal helper loading a proposal that can still be sponsored
+        fn waiting_for_sponsorship(&self, proposal_id: u32) -> Result<Proposal> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Proposed {
+                return Err(Error::NotSponsorable);
+            }
+            if proposal.sponsorship_deadline.is_some_and(|deadline| self.clock_now(&proposal.clock) > deadline) {
+                return Err(Error::ProposalLapsed);
+            }
+            Ok(proposal)
+        }
+
+        /// Internal helper checking whether a proposal's deposit sponsors it on its own
+        fn deposit_sponsors(&self, proposal_id: u32, config: &SponsorshipConfig) -> bool {
+            config.deposit_threshold.is_some_and(|threshold| {
+                self.proposal_deposits.get(proposal_id).unwrap_or(0) >= threshold
+            })
+        }
+
+        /// Internal helper opening a sponsored proposal, with its voting period starting now.
+        /// A draft whose voting start is still ahead goes back to waiting for it instead.
+        fn open_sponsored(&self, proposal: &mut Proposal) -> Result<()> {
+            if proposal.voting_start > self.clock_now(&proposal.clock) {
+                proposal.sponsorship_deadline = None;
+                proposal.status = ProposalStatus::Pending;
+                return Ok(());
+            }
+            let (voting_start, voting_end, execution_time) = self.voting_schedule(&proposal.governance_params, &proposal.clock)?;
+            proposal.voting_start = voting_start;
+            proposal.voting_end = voting_end;
+            proposal.execution_time = execution_time;
+            proposal.sponsorship_deadline = None;
+            proposal.status = ProposalStatus::Active;
+            Ok(())
+        }
+
+        /// Internal helper to check a new proposal against its type's policy and hold the deposit sent with it
+        fn enforce_proposal_policy(&mut self, proposal: &Proposal) -> Result<()> {
+            let policy = self.proposal_policies
+                .iter()
+                .find(|(proposal_type, _)| *proposal_type == proposal.proposal_type)
+                .map(|(_, policy)| policy.clone());
+            let params = &proposal.governance_params;
+            if let Some(policy) = &policy {
+                if params.voting_period.to_blocks() < policy.min_voting_period.to_blocks()
+                    || params.quorum_threshold.to_percentage() < policy.min_quorum.to_percentage()
+                    || params.execution_delay.to_blocks() < policy.min_execution_delay.to_blocks()
+                    || (params.early_decision && !policy.allow_early_decision)
+                {
+                    return Err(Error::PolicyViolation);
+                }
+            }
+            
+            let deposit = policy.map_or(0, |policy| policy.deposit);
+            let transferred = self.env().transferred_value();
+            if transferred < U256::from(deposit) {
+                return Err(Error::InsufficientDeposit);
+            }
+            // Anything sent beyond the deposit is held and returned with it
+            let held = Balance::try_from(transferred).map_err(|_| Error::ArithmeticOverflow)?;
+            self.hold_deposit(proposal.id, proposal.proposer, held)
+        }
+
+        /// Internal helper holding an amount put towards a proposal's deposit by a contributor
+        fn hold_deposit(&mut self, proposal_id: u32, contributor: H160, amount: Balance) -> Result<()> {
+            if amount == 0 {
+                return Ok(());
+            }
+            
+            let mut contributors = self.deposit_contributors.get(proposal_id).unwrap_or_default();
+            if !contributors.contains(&contributor) {
+                if contributors.len() >= MAX_DEPOSIT_CONTRIBUTORS {
+                    return Err(Error::TooManyDepositContributors);
+                }
+                contributors.push(contributor);
+                self.deposit_contributors.insert(proposal_id, &contributors);
+            }
+            let contribution = self.deposit_contributions.get((proposal_id, contributor)).unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.deposit_contributions.insert((proposal_id, contributor), &contribution);
+            
+            let deposit = self.proposal_deposits.get(proposal_id).unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.proposal_deposits.insert(proposal_id, &deposit);
+            self.held_deposits = self.held_deposits.checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Internal helper to settle a proposal's deposit: each contributor's share is returned
+        /// to them as a claim, or the whole deposit is kept by the treasury when `refund` is false
+        fn settle_deposit(&mut self, proposal: &Proposal, refund: bool, current_block: u32) -> Result<()> {
+            let Some(deposit) = self.proposal_deposits.take(proposal.id) else {
+                return Ok(());
+            };
+            
+            self.held_deposits = self.held_deposits.saturating_sub(deposit);
+            for contributor in self.deposit_contributors.take(proposal.id).unwrap_or_default() {
+                let contribution = self.deposit_contributions.take((proposal.id, contributor)).unwrap_or(0);
+                if refund {
+                    self.open_claim(PayoutSource::Proposal(proposal.id), contributor, contribution, &Asset::Native, current_block)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Cast a vote on a proposal
+        #[ink(message)]
+        pub fn vote(&mut self, proposal_id: u32, option_index: u32) -> Result<VoteOutcome> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            
+            // Check if voter is registered
+            if !self.registered_voters.get(caller).unwrap_or(false) {
+                return Err(Error::NotRegisteredVoter);
+            }
+            if let Some(status) = self.settle_before_vote(proposal_id)? {
+                return Ok(VoteOutcome::VotingClosed(status));
+            }
+            
+            // Get proposal
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            
+            // Validate proposal status and timing
+            self.open_voting_if_started(&mut proposal);
+            if proposal.status == ProposalStatus::Pending {
+                return Err(Error::VotingNotStarted);
+            }
+            if !matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Confirming) {
+                return Err(Error::ProposalNotActive);
+            }
+            if self.clock_now(&proposal.clock) > proposal.voting_end {
+                return Err(Error::VotingPeriodEnded);
+            }
+            if proposal.seats.is_some() {
+                return Err(Error::ApprovalBallotRequired);
+            }
+            // Voters listed before the upgrade that added positions predate every open proposal
+            if proposal.governance_params.early_decision
+                && self.voter_positions.get(caller).unwrap_or(0) >= proposal.electorate
+            {
+                return Err(Error::RegisteredAfterProposal);
+            }
+            
+            // Check if user already voted
+            if self.votes.get((proposal_id, caller)).is_some() {
+                return Err(Error::AlreadyVoted);
+            }
+            
+            // Validate option index
+            if option_index as usize >= proposal.voting_options.options.len() {
+                return Err(Error::InvalidOptionIndex);
+            }
+            
+            // Create vote record
+            let option_text = proposal.voting_options.options[option_index as usize].clone();
+            let vote = Vote {
+                voter: caller,
+                choice: VoteChoice {
+                    option_index,
+                    option_text: option_text.clone(),
+                },
+                timestamp: current_block,
+                weight: 1, // Simple voting weight of 1 for now
+            };
+            let winner_before = self.resolve_winner(&proposal);
+            
+            // Update vote counts
+            proposal.vote_counts[option_index as usize] = proposal.vote_counts[option_index as usize]
+                .checked_add(vote.weight)
+                .ok_or(Error::ArithmeticOverflow)?;
+            proposal.total_voters = proposal.total_voters.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            proposal.last_vote_ordinals[option_index as usize] = proposal.total_voters;
+            
+            // Store vote and updated proposal
+            self.votes.insert((proposal_id, caller), &vote);
+            self.proposal_voters.insert((proposal_id, proposal.total_voters - 1), &caller);
+            
+            // A late vote that overturns the winner gives the other side time to respond;
+            // the first votes only establish one
+            let winner_after = self.resolve_winner(&proposal);
+            if winner_before.is_some() && winner_after != winner_before {
+                self.extend_sniped_vote(&mut proposal)?;
+            }
+            // A new leader has to hold for a confirm period of its own
+            if winner_after != winner_before {
+                proposal.confirm_start = None;
+            }
+            let now = self.clock_now(&proposal.clock);
+            self.track_confirmation(&mut proposal, now)?;
+            self.proposals.insert(proposal_id, &proposal);
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(VoteOutcome::Cast)
+        }
+
+        /// Internal helper lazily finalizing a proposal before a vote.
+        /// Returns its new status if voting on it has just closed.
+        fn settle_before_vote(&mut self, proposal_id: u32) -> Result<Option<ProposalStatus>> {
+            let status = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?
+                .status;
+            self.finalize_proposal(proposal_id)?;
+            let settled = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?
+                .status;
+            let closed = settled != status
+                && !matches!(settled, ProposalStatus::Active | ProposalStatus::Confirming);
+            Ok(closed.then_some(settled))
+        }
+
+        /// Internal helper extending voting when the winner changed inside the anti-sniping window
+        fn extend_sniped_vote(&self, proposal: &mut Proposal) -> Result<()> {
+            let Some(config) = self.anti_sniping.as_ref() else {
+                return Ok(());
+            };
+            if proposal.voting_extensions.len() >= config.max_extensions as usize {
+                return Ok(());
+            }
+            
+            let window = self.clock_span(&proposal.clock, config.window_blocks);
+            let extension = self.clock_span(&proposal.clock, config.extension_blocks);
+            let now = self.clock_now(&proposal.clock);
+            if proposal.voting_end.saturating_sub(now) >= window {
+                return Ok(());
+            }
+            
+            proposal.voting_end = proposal.voting_end.checked_add(extension)
+                .ok_or(Error::ArithmeticOverflow)?;
+            proposal.execution_time = proposal.execution_time.checked_add(extension)
+                .ok_or(Error::ArithmeticOverflow)?;
+            proposal.voting_extensions.push(now);
+            Ok(())
+        }
+
+        /// Internal helper moving a proposal in or out of its confirm period, returning whether it has been confirmed
+        fn track_confirmation(&self, proposal: &mut Proposal, now: u64) -> Result<bool> {
+            let Some(confirm_period) = self.confirm_period else {
+                return Ok(false);
+            };
+            // Elections and proposals past `voting_end` are decided by normal finalization
+            if proposal.seats.is_some()
+                || !matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Confirming)
+                || now > proposal.voting_end
+            {
+                return Ok(false);
+            }
+            
+            // Proposals carrying an action are approved only by "Aye", the first option, leading;
+            // any other proposal is decided by whichever option leads
+            let approved = self.has_reached_quorum_internal(proposal)?
+                && if Self::carries_action(proposal) {
+                    self.approves_action(proposal)
+                } else {
+                    self.resolve_winner(proposal).is_some()
+                };
+            if !approved {
+                proposal.status = ProposalStatus::Active;
+                proposal.confirm_start = None;
+                return Ok(false);
+            }
+            
+            proposal.status = ProposalStatus::Confirming;
+            let confirm_start = *proposal.confirm_start.get_or_insert(now);
+            let confirm_end = confirm_start.checked_add(self.clock_span(&proposal.clock, confirm_period))
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(now >= confirm_end)
+        }
+
+        /// Internal helper checking whether the voters yet to vote can no longer change an opted-in proposal's outcome
+        fn outcome_settled(&self, proposal: &Proposal, now: u64) -> bool {
+            if !proposal.governance_params.early_decision
+                || proposal.seats.is_some()
+                || !matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Confirming)
+                || now > proposal.voting_end
+            {
+                return false;
+            }
+            
+            // Each voter of the snapshot electorate yet to vote carries a weight of 1
+            let electorate = proposal.electorate;
+            let remaining = u128::from(electorate.saturating_sub(proposal.total_voters));
+            let cast: u128 = proposal.vote_counts.iter().sum();
+            let quorum_percentage = proposal.governance_params.quorum_threshold.to_percentage();
+            let required_votes = (electorate as u128 * quorum_percentage as u128) / 100;
+            
+            // With everyone voted nothing can change; otherwise the leader must be uncatchable
+            if remaining == 0 {
+                return true;
+            }
+            if cast < required_votes {
+                return false;
+            }
+            let mut counts = proposal.vote_counts.clone();
+            counts.sort_unstable_by(|a, b| b.cmp(a));
+            let leader = counts.first().copied().unwrap_or(0);
+            let runner_up = counts.get(1).copied().unwrap_or(0);
+            leader > runner_up.saturating_add(remaining)
+        }
+
+        /// Internal helper converting a number of blocks to a duration on the given clock
+        fn clock_span(&self, clock: &SchedulingClock, blocks: u32) -> u64 {
+            match clock {
+                SchedulingClock::BlockNumber => u64::from(blocks),
+                SchedulingClock::Timestamp => u64::from(blocks) * u64::from(self.block_time_ms),
+            }
+        }
+
+        /// Cast an approval ballot on an election, approving any number of candidates
+        #[ink(message)]
+        pub fn vote_approval(&mut self, proposal_id: u32, approvals: Vec<u32>) -> Result<VoteOutcome> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            
+            // Check if voter is registered
+            if !self.registered_voters.get(caller).unwrap_or(false) {
+                return Err(Error::NotRegisteredVoter);
+            }
+            if let Some(status) = self.settle_before_vote(proposal_id)? {
+                return Ok(VoteOutcome::VotingClosed(status));
+            }
+            
+            // Get proposal
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            
+            // Validate proposal status and timing
+            self.open_voting_if_started(&mut proposal);
+            if proposal.status == ProposalStatus::Pending {
+                return Err(Error::VotingNotStarted);
+            }
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+            if self.clock_now(&proposal.clock) > proposal.voting_end {
+                return Err(Error::VotingPeriodEnded);
+            }
+            if proposal.seats.is_none() {
+                return Err(Error::NotAnElection);
+            }
+            
+            // Check if user already voted
+            if self.election_ballots.contains((proposal_id, caller)) {
+                return Err(Error::AlreadyVoted);
+            }
+            if proposal.total_voters >= MAX_ELECTION_BALLOTS {
+                return Err(Error::ElectionFull);
+            }
+            
+            // Validate approvals: non-empty, in range and without duplicates
+            if approvals.is_empty() {
+                return Err(Error::EmptyBallot);
+            }
+            for (position, &option_index) in approvals.iter().enumerate() {
+                if option_index as usize >= proposal.voting_options.options.len()
+                    || approvals[..position].contains(&option_index)
+                {
+                    return Err(Error::InvalidOptionIndex);
+                }
+            }
+            
+            let ballot = ApprovalBallot {
+                voter: caller,
+                approvals,
+                timestamp: current_block,
+                weight: 1, // Simple voting weight of 1 for now
+            };
+            
+            // Update approval counts
+            for &option_index in &ballot.approvals {
+                proposal.vote_counts[option_index as usize] = proposal.vote_counts[option_index as usize]
+                    .checked_add(ballot.weight)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            proposal.total_voters = proposal.total_voters.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            
+            // Store ballot and updated proposal
+            self.election_ballots.insert((proposal_id, caller), &ballot);
+            self.proposal_voters.insert((proposal_id, proposal.total_voters - 1), &caller);
+            self.proposals.insert(proposal_id, &proposal);
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(VoteOutcome::Cast)
+        }
+
+        /// Update proposal status based on voting results
+        #[ink(message)]
+        pub fn update_proposal_status(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            self.finalize_proposal(proposal_id)
+        }
+
+        /// Settle the status of several proposals in one call, reporting the result for each id.
+        /// Elections whose tally would take the batch past `MAX_FINALIZE_BATCH_BALLOTS` are deferred.
+        #[ink(message)]
+        pub fn finalize_batch(&mut self, proposal_ids: Vec<u32>) -> Result<Vec<FinalizeOutcome>> {
+            self.migrate_storage()?;
+            self.ensure_not_importing()?;
+            if proposal_ids.len() > MAX_FINALIZE_BATCH {
+                return Err(Error::BatchTooLarge);
+            }
+            
+            let mut outcomes = Vec::with_capacity(proposal_ids.len());
+            let mut ballots_tallied = 0u32;
+            for proposal_id in proposal_ids {
+                let Some(proposal) = self.proposals.get(proposal_id) else {
+                    outcomes.push(FinalizeOutcome::NotFound);
+                    continue;
+                };
+                
+                // Tallying an election walks all of its ballots
+                let due = matches!(proposal.status, ProposalStatus::Pending | ProposalStatus::Active)
+                    && self.clock_now(&proposal.clock) > proposal.voting_end;
+                if proposal.seats.is_some() && due {
+                    let ballots = ballots_tallied.saturating_add(proposal.total_voters);
+                    if ballots > MAX_FINALIZE_BATCH_BALLOTS {
+                        outcomes.push(FinalizeOutcome::Deferred);
+                        continue;
+                    }
+                    ballots_tallied = ballots;
+                }
+                
+                self.finalize_proposal(proposal_id)?;
+                let status = self.proposals.get(proposal_id)
+                    .ok_or(Error::ProposalNotFound)?
+                    .status;
+                outcomes.push(if status == proposal.status {
+                    FinalizeOutcome::Unchanged(status)
+                } else {
+                    FinalizeOutcome::Settled(status)
+                });
+            }
+            Ok(outcomes)
+        }
+
+        /// Internal helper settling a proposal whose voting has ended, lapsed or been decided early
+        fn finalize_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.ensure_not_importing()?;
+            let current_block = self.env().block_number();
+            
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            
+            // Un-sponsored proposals lapse, forfeiting any deposit to the treasury
+            if proposal.status == ProposalStatus::Proposed {
+                if proposal.sponsorship_deadline.is_some_and(|deadline| self.clock_now(&proposal.clock) > deadline) {
+                    proposal.status = ProposalStatus::Expired;
+                    self.settle_deposit(&proposal, false, current_block)?;
+                    self.proposals.insert(proposal_id, &proposal);
+                }
+                return Ok(());
+            }
+            
+            // Only update if currently active and voting period has ended or the proposal is confirmed
+            let opened = self.open_voting_if_started(&mut proposal);
+            let now = self.clock_now(&proposal.clock);
+            let confirm_start = proposal.confirm_start;
+            if self.track_confirmation(&mut proposal, now)? || self.outcome_settled(&proposal, now) {
+                // Deciding early closes voting now, keeping the execution delay
+                let execution_delay = proposal.execution_time.saturating_sub(proposal.voting_end);
+                proposal.voting_end = now;
+                proposal.execution_time = now.checked_add(execution_delay)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            } else if !matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Confirming)
+                || now <= proposal.voting_end
+            {
+                if opened || proposal.confirm_start != confirm_start {
+                    self.proposals.insert(proposal_id, &proposal);
+                }
+                return Ok(());
+            }
+            
+            let _old_status = proposal.status.clone();
+            
+            // Check if quorum is reached
+            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
+            
+            if quorum_reached && proposal.seats.is_some() {
+                // Allocate seats proportionally among approved candidates
+                let winners = self.run_phragmen(&proposal)?;
+                proposal.status = if winners.is_empty() {
+                    ProposalStatus::Rejected
+                } else {
+                    ProposalStatus::Passed
+                };
+                self.election_winners.insert(proposal_id, &winners);
+            } else if quorum_reached {
+                let leading_options = Self::leading_options(&proposal);
+                
+                // A clear winner (or a tie settled by the tie policy) passes, unless a proposal
+                // carrying an action was voted down
+                if self.resolve_winner(&proposal).is_some() {
+                    proposal.status = if Self::carries_action(&proposal) && !self.approves_action(&proposal) {
+                        ProposalStatus::Rejected
+                    } else {
+                        ProposalStatus::Passed
+                    };
+                } else if leading_options.len() > 1
+                    && proposal.governance_params.tie_policy == TiePolicy::Runoff
+                {
+                    let runoff_id = self.create_runoff(&proposal, &leading_options)?;
+                    proposal.runoff_proposal = Some(runoff_id);
+                    proposal.status = ProposalStatus::RunoffScheduled;
+                } else {
+                    proposal.status = ProposalStatus::Rejected;
+                }
+            } else {
+                proposal.status = ProposalStatus::Rejected;
+            }
+            
+            self.settle_deposit(&proposal, quorum_reached, current_block)?;
+            
+            // Approved spends are reserved until they are paid or vetoed. Other spends may
+            // have reserved the funds since this one was proposed, in which case it fails.
+            if proposal.status == ProposalStatus::Passed {
+                if let Some(spend) = self.committed_spend(&proposal) {
+                    if self.ensure_spend_affordable(&spend).is_ok() {
+                        self.add_liability(&spend)?;
+                    } else {
+                        proposal.status = ProposalStatus::Rejected;
+                    }
+                }
+            }
+            
+            // Store updated proposal
+            self.proposals.insert(proposal_id, &proposal);
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(())
+        }
+
+        /// Execute a passed proposal
+        #[ink(message)]
+        pub fn execute_proposal(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            self.finalize_proposal(proposal_id)?;
+            
+            let current_block = self.env().block_number();
+            
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            
+            // Validate proposal can be executed
+            if proposal.status != ProposalStatus::Passed {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            if self.clock_now(&proposal.clock) < proposal.execution_time {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            
+            // Enforce outflow limits; `report_outflow_breach` trips the circuit breaker on a breach
+            if let Some(spend) = self.committed_spend(&proposal) {
+                if self.outflow_paused {
+                    return Err(Error::TreasuryPaused);
+                }
+                if !self.outflow_within_limits(&spend.asset, &proposal.proposal_type, spend.amount, current_block)? {
+                    return Err(Error::OutflowLimitExceeded);
+                }
+            }
+            
+            // Apply any council change the proposal carries
+            if let Some(change) = proposal.council_change.clone() {
+                self.apply_council_change(&proposal, change)?;
+            }
+            
+            // Apply any configuration changes the proposal carries
+            if !proposal.config_changes.is_empty() && self.approves_action(&proposal) {
+                for change in proposal.config_changes.clone() {
+                    self.apply_config_change(change)?;
+                }
+            }
+            
+            // Swap in the new code; it takes effect from the next call
+            if let Some(code_hash) = proposal.code_upgrade {
+                if self.approves_action(&proposal) {
+                    self.env().set_code_hash(&code_hash)
+                        .map_err(|_| Error::UpgradeFailed)?;
+                }
+            }
+            
+            // Apply any pot change the proposal carries
+            if let Some(action) = proposal.pot_action.clone() {
+                if self.approves_action(&proposal) {
+                    self.apply_pot_action(action)?;
+                }
+            }
+            
+            // Cancel a stream the proposal targets
+            if let Some(stream_id) = proposal.cancel_stream {
+                if self.approves_action(&proposal) {
+                    self.cancel_stream(stream_id, current_block)?;
+                }
+            }
+            
+            // Update status to executed
+            proposal.status = ProposalStatus::Executed;
+            
+            // Pay out an approved spend, or escrow it when it is a milestone grant or stream
+            if let Some(spend) = self.committed_spend(&proposal) {
+                self.ensure_pot_spend_approved(proposal_id, &spend)?;
+                let queued = proposal.grant_terms.is_none()
+                    && proposal.bounty_terms.is_none()
+                    && proposal.payout_schedule.is_none()
+                    && self.spend_period_config.is_some();
+                if !queued {
+                    self.record_outflow(&spend.asset, &proposal.proposal_type, spend.amount, current_block);
+                }
+                if let Some(terms) = proposal.grant_terms.clone() {
+                    self.escrow_grant(proposal_id, terms, spend.amount, current_block)?;
+                    self.release_liability(&spend)?;
+                } else if let Some(terms) = proposal.bounty_terms.clone() {
+                    self.fund_bounty(proposal_id, terms, spend.amount, current_block)?;
+                    self.release_liability(&spend)?;
+                } else if let Some(schedule) = proposal.payout_schedule.clone() {
+                    // Streamed pot funds leave the pot when they are escrowed
+                    self.debit_pot(&spend)?;
+                    self.open_stream(proposal_id, &spend, schedule, current_block)?;
+                    self.release_liability(&spend)?;
+                } else if self.spend_period_config.is_some() {
+                    // Paid at the next spend period boundary, staying reserved until then.
+                    // Its outflow is recorded when it is paid.
+                    self.approvals_queue.push(proposal_id);
+                    proposal.status = ProposalStatus::Queued;
+                } else {
+                    self.debit_pot(&spend)?;
+                    self.open_spend_claims(proposal_id, &spend, current_block)?;
+                    self.release_liability(&spend)?;
+                }
+            }
+            
+            self.proposals.insert(proposal_id, &proposal);
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(())
+        }
+
+        // Upgrades
+
+        /// Bring storage written by an earlier release up to `CONTRACT_VERSION`. Every state-changing
+        /// message does this first, so it runs on the first call after an upgrade; calling it directly
+        /// lets governance pay for the migration up front.
+        /// Root storage is decoded before any message runs; only mapped data can be migrated.
+        #[ink(message)]
+        pub fn migrate(&mut self) -> Result<()> {
+            self.migrate_storage()
+        }
+
+        /// Internal helper migrating storage written by an earlier release, a no-op once it is current
+        fn migrate_storage(&mut self) -> Result<()> {
+            if self.storage_version >= CONTRACT_VERSION {
+                return Ok(());
+            }
+            
+            // Version 0 proposals predate every proposal field added since; votes and voters are unchanged
+            if self.storage_version < 1 {
+                let key = ink::storage::traits::StorageKey::key(&self.proposals);
+                for proposal_id in self.proposal_ids.clone() {
+                    let legacy = ink::env::get_contract_storage::<_, LegacyProposal>(&(key, proposal_id))
+                        .ok()
+                        .flatten();
+                    if let Some(legacy) = legacy {
+                        self.proposals.insert(proposal_id, &self.upgrade_legacy_proposal(legacy));
+                    }
+                }
+            }
+            
+            self.storage_version = CONTRACT_VERSION;
+            Ok(())
+        }
+
+        /// Internal helper converting a version 0 proposal, which always ran on the block clock
+        fn upgrade_legacy_proposal(&self, legacy: LegacyProposal) -> Proposal {
+            let option_count = legacy.voting_options.options.len();
+            Proposal {
+                id: legacy.id,
+                title: legacy.title,
+                description: legacy.description,
+                proposal_type: legacy.proposal_type,
+                governance_params: GovernanceParameters {
+                    voting_period: legacy.governance_params.voting_period,
+                    quorum_threshold: legacy.governance_params.quorum_threshold,
+                    execution_delay: legacy.governance_params.execution_delay,
+                    tie_policy: TiePolicy::Reject,
+                    early_decision: false,
+                },
+                voting_options: legacy.voting_options,
+                proposer: legacy.proposer,
+                created_at: legacy.created_at,
+                voting_start: u64::from(legacy.created_at),
+                voting_end: u64::from(legacy.voting_end),
+                execution_time: u64::from(legacy.execution_time),
+                clock: SchedulingClock::BlockNumber,
+                status: match legacy.status {
+                    LegacyProposalStatus::Active => ProposalStatus::Active,
+                    LegacyProposalStatus::Passed => ProposalStatus::Passed,
+                    LegacyProposalStatus::Rejected => ProposalStatus::Rejected,
+                    LegacyProposalStatus::Executed => ProposalStatus::Executed,
+                    LegacyProposalStatus::Expired => ProposalStatus::Expired,
+                },
+                vote_counts: legacy.vote_counts,
+                total_voters: legacy.total_voters,
+                last_vote_ordinals: vec![0; option_count],
+                parent_proposal: None,
+                runoff_proposal: None,
+                seats: None,
+                council_change: None,
+                spend: None,
+                grant_terms: None,
+                payout_schedule: None,
+                cancel_stream: None,
+                pot_action: None,
+                bounty_terms: None,
+                config_changes: Vec::new(),
+                code_upgrade: None,
+                sponsorship_deadline: None,
+                voting_extensions: Vec::new(),
+                confirm_start: None,
+                electorate: self.total_voters,
+            }
+        }
+
+        // State export and import
+
+        /// Export the contract-wide part of the governance state.
+        /// Claims, escrows, pots, deposits, queued spends, unpaid tips and token liabilities are
+        /// not carried over, so the export is refused until they have all been settled.
+        #[ink(message)]
+        pub fn export_header(&self) -> Result<StateExportHeader> {
+            if self.has_open_financial_state() {
+                return Err(Error::OpenFinancialState);
+            }
+            
+            Ok(StateExportHeader {
+                version: EXPORT_FORMAT_VERSION,
+                next_proposal_id: self.next_proposal_id,
+                proposal_count: self.proposal_ids.len() as u32,
+                voter_count: self.voter_list_len,
+                total_voters: self.total_voters,
+                council_members: self.council_members.clone(),
+                council_prime: self.council_prime,
+                block_time_ms: self.block_time_ms,
+                next_spend_period: self.next_spend_period,
+                spend_period_index: self.spend_period_index,
+                next_tip_id: self.next_tip_id,
+                config: self.config_snapshot(),
+            })
+        }
+
+        /// Internal helper checking for funds or payouts a state export would leave behind
+        fn has_open_financial_state(&self) -> bool {
+            self.escrowed_funds > 0
+                || self.held_deposits > 0
+                || !self.pot_names.is_empty()
+                || !self.approvals_queue.is_empty()
+                || self.open_tips > 0
+                || self.outflow_paused
+                || self.accepted_assets.iter().any(|&token| self.asset_liabilities.get(token).unwrap_or(0) > 0)
+        }
+
+        /// Internal helper listing the config changes that rebuild the current settings
+        fn config_snapshot(&self) -> Vec<ConfigChange> {
+            let mut config: Vec<ConfigChange> = self.proposal_policies
+                .iter()
+                .map(|(proposal_type, policy)| ConfigChange::SetProposalPolicy {
+                    proposal_type: proposal_type.clone(),
+                    policy: Some(policy.clone()),
+                })
+                .collect();
+            config.extend([
+                ConfigChange::SetMaxVotingOptions(self.max_voting_options),
+                ConfigChange::SetMembershipMode(self.membership_mode.clone()),
+                ConfigChange::SetSponsorship(self.sponsorship.clone()),
+                ConfigChange::SetAntiSniping(self.anti_sniping.clone()),
+                ConfigChange::SetConfirmPeriod(self.confirm_period),
+                ConfigChange::SetClock(self.clock.clone()),
+                ConfigChange::SetGuardians(self.guardians.clone()),
+                ConfigChange::SetOutflowLimits(self.outflow_limits.clone()),
+                ConfigChange::SetSpendPeriodConfig(self.spend_period_config.clone()),
+                ConfigChange::SetMaxTip(self.max_tip),
+            ]);
+            config.extend(self.accepted_assets.iter().map(|&token| ConfigChange::AddAcceptedAsset(token)));
+            config
+        }
+
+        /// Export a page of proposals, in creation order, with everything recorded against them
+        #[ink(message)]
+        pub fn export_proposals(&self, offset: u32, limit: u32) -> Vec<ExportedProposal> {
+            self.proposal_ids
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .filter_map(|proposal_id| self.proposals.get(proposal_id))
+                .map(|proposal| {
+                    let voters: Vec<H160> = (0..proposal.total_voters)
+                        .filter_map(|index| self.proposal_voters.get((proposal.id, index)))
+                        .collect();
+                    ExportedProposal {
+                        votes: voters.iter().filter_map(|&voter| self.votes.get((proposal.id, voter))).collect(),
+                        approval_ballots: voters.iter()
+                            .filter_map(|&voter| self.election_ballots.get((proposal.id, voter)))
+                            .collect(),
+                        election_winners: self.election_winners.get(proposal.id),
+                        seconds: self.proposal_seconds.get(proposal.id).unwrap_or_default(),
+                        council_motions: [CouncilMotionKind::Veto, CouncilMotionKind::FastTrack]
+                            .into_iter()
+                            .filter_map(|kind| self.council_motions.get((proposal.id, kind)))
+                            .collect(),
+                        grant: self.grants.get(proposal.id),
+                        stream: self.streams.get(proposal.id),
+                        bounty: self.bounties.get(proposal.id),
+                        proposal,
+                    }
+                })
+                .collect()
+        }
+
+        /// Export a page of registered voters, in registration order
+        #[ink(message)]
+        pub fn export_voters(&self, offset: u32, limit: u32) -> Vec<H160> {
+            let end = offset.saturating_add(limit).min(self.voter_list_len);
+            (offset..end)
+                .filter_map(|index| self.voter_list.get(index))
+                .collect()
+        }
+
+        /// Export a page of tips, in reporting order
+        #[ink(message)]
+        pub fn export_tips(&self, offset: u32, limit: u32) -> Vec<Tip> {
+            let end = offset.saturating_add(limit).min(self.next_tip_id);
+            (offset..end)
+                .filter_map(|tip_id| self.tips.get(tip_id))
+                .collect()
+        }
+
+        /// Export a page of spend-period reports, in period order
+        #[ink(message)]
+        pub fn export_spend_reports(&self, offset: u32, limit: u32) -> Vec<SpendPeriodReport> {
+            let end = offset.saturating_add(limit).min(self.spend_period_index);
+            (offset..end)
+                .filter_map(|period| self.spend_reports.get(period))
+                .collect()
+        }
+
+        /// Import the contract-wide state from an export (owner only, during import)
+        #[ink(message)]
+        pub fn import_header(&mut self, header: StateExportHeader) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            if header.version != EXPORT_FORMAT_VERSION {
+                return Err(Error::UnsupportedExportVersion);
+            }
+            
+            if header.block_time_ms == 0 {
+                return Err(Error::InvalidConfigChange);
+            }
+            
+            self.next_proposal_id = header.next_proposal_id;
+            self.total_voters = header.total_voters;
+            self.council_members = header.council_members;
+            self.council_prime = header.council_prime;
+            self.block_time_ms = header.block_time_ms;
+            self.next_spend_period = header.next_spend_period;
+            self.spend_period_index = header.spend_period_index;
+            self.next_tip_id = header.next_tip_id;
+            for change in header.config {
+                self.apply_config_change(change)?;
+            }
+            Ok(())
+        }
+
+        /// Import a page of registered voters (owner only, during import)
+        #[ink(message)]
+        pub fn import_voters(&mut self, voters: Vec<H160>) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            for voter in voters {
+                if !self.registered_voters.get(voter).unwrap_or(false) {
+                    self.list_voter(voter)?;
+                }
+            }
+            Ok(())
+        }
+
+        /// Import a page of proposals under their original ids (owner only, during import).
+        /// The header goes first, since ids must be below its `next_proposal_id`.
+        /// Funds for passed spends from the main treasury are reserved again.
+        #[ink(message)]
+        pub fn import_proposals(&mut self, proposals: Vec<ExportedProposal>) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            for exported in proposals {
+                let proposal_id = exported.proposal.id;
+                if proposal_id >= self.next_proposal_id || self.proposals.contains(proposal_id) {
+                    return Err(Error::InvalidProposal);
+                }
+                
+                for (index, vote) in exported.votes.into_iter().enumerate() {
+                    self.votes.insert((proposal_id, vote.voter), &vote);
+                    self.proposal_voters.insert((proposal_id, index as u32), &vote.voter);
+                }
+                for (index, ballot) in exported.approval_ballots.into_iter().enumerate() {
+                    self.election_ballots.insert((proposal_id, ballot.voter), &ballot);
+                    self.proposal_voters.insert((proposal_id, index as u32), &ballot.voter);
+                }
+                if let Some(winners) = exported.election_winners {
+                    self.election_winners.insert(proposal_id, &winners);
+                }
+                if !exported.seconds.is_empty() {
+                    self.proposal_seconds.insert(proposal_id, &exported.seconds);
+                }
+                for motion in exported.council_motions {
+                    if motion.proposal_id != proposal_id {
+                        return Err(Error::InvalidProposal);
+                    }
+                    self.council_motions.insert((proposal_id, motion.kind.clone()), &motion);
+                }
+                if let Some(grant) = exported.grant {
+                    self.grants.insert(proposal_id, &grant);
+                }
+                if let Some(stream) = exported.stream {
+                    self.streams.insert(proposal_id, &stream);
+                }
+                if let Some(bounty) = exported.bounty {
+                    self.bounties.insert(proposal_id, &bounty);
+                }
+                
+                if exported.proposal.status == ProposalStatus::Passed {
+                    if let Some(spend) = self.committed_spend(&exported.proposal).filter(|spend| spend.pot.is_none()) {
+                        self.add_liability(&spend)?;
+                    }
+                }
+                self.proposals.insert(proposal_id, &exported.proposal);
+                self.proposal_ids.push(proposal_id);
+            }
+            Ok(())
+        }
+
+        /// Import a page of paid tips under their original ids (owner only, during import)
+        #[ink(message)]
+        pub fn import_tips(&mut self, tips: Vec<Tip>) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            for tip in tips {
+                if tip.id >= self.next_tip_id || self.tips.contains(tip.id) {
+                    return Err(Error::TipNotFound);
+                }
+                // Unpaid tips are refused by the export
+                if tip.paid.is_none() {
+                    return Err(Error::OpenFinancialState);
+                }
+                self.tips.insert(tip.id, &tip);
+            }
+            Ok(())
+        }
+
+        /// Import a page of spend-period reports (owner only, during import)
+        #[ink(message)]
+        pub fn import_spend_reports(&mut self, reports: Vec<SpendPeriodReport>) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            for report in reports {
+                if report.period >= self.spend_period_index {
+                    return Err(Error::InvalidSpendPeriodConfig);
+                }
+                self.spend_reports.insert(report.period, &report);
+            }
+            Ok(())
+        }
+
+        /// Close the import, after which the import messages are rejected (owner only)
+        #[ink(message)]
+        pub fn finish_import(&mut self) -> Result<()> {
+            self.migrate_storage()?;
+            self.ensure_importing()?;
+            self.importing = false;
+            Ok(())
+        }
+
+        /// Internal helper to check the owner is importing into a fresh deployment
+        fn ensure_importing(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotAuthorized);
+            }
+            if !self.importing {
+                return Err(Error::ImportClosed);
+            }
+            Ok(())
+        }
+
+        /// Internal helper keeping imported ids and votes out of reach until the import is finished
+        fn ensure_not_importing(&self) -> Result<()> {
+            if self.importing {
+                return Err(Error::ImportInProgress);
+            }
+            Ok(())
+        }
+
+        // Configuration
+
+        /// Internal helper to apply a configuration change from an executed proposal
+        fn apply_config_change(&mut self, change: ConfigChange) -> Result<()> {
+            match change {
+                ConfigChange::SetProposalPolicy { proposal_type, policy } => {
+                    self.proposal_policies.retain(|(existing, _)| *existing != proposal_type);
+                    if let Some(policy) = policy {
+                        self.proposal_policies.push((proposal_type, policy));
+                    }
+                }
+                ConfigChange::SetMaxVotingOptions(max) => {
+                    // Aye/Nay payload proposals need at least two options
+                    if max < 2 {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    self.max_voting_options = max;
+                }
+                ConfigChange::SetMembershipMode(mode) => self.membership_mode = mode,
+                ConfigChange::SetSponsorship(sponsorship) => {
+                    if sponsorship.as_ref().is_some_and(|config| config.lapse_blocks == 0) {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    self.sponsorship = sponsorship;
+                }
+                ConfigChange::SetAntiSniping(anti_sniping) => {
+                    if anti_sniping.as_ref().is_some_and(|config| config.extension_blocks == 0) {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    self.anti_sniping = anti_sniping;
+                }
+                ConfigChange::SetConfirmPeriod(confirm_period) => {
+                    if confirm_period == Some(0) {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    self.confirm_period = confirm_period;
+                }
+                ConfigChange::SetClock(clock) => self.clock = clock,
+                ConfigChange::AdmitVoters(voters) => {
+                    if !Self::is_valid_account_list(&voters) {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    for voter in voters {
+                        if !self.registered_voters.get(voter).unwrap_or(false) {
+                            self.add_voter(voter)?;
+                        }
+                    }
+                }
+                ConfigChange::SetGuardians(guardians) => {
+                    if !Self::is_valid_account_list(&guardians) {
+                        return Err(Error::InvalidConfigChange);
+                    }
+                    self.guardians = guardians;
+                }
+                ConfigChange::SetOutflowLimits(limits) => self.apply_outflow_limits(limits)?,
+                ConfigChange::ResumeOutflows => self.outflow_paused = false,
+                ConfigChange::SetSpendPeriodConfig(config) => self.apply_spend_period_config(config)?,
+                ConfigChange::AddAcceptedAsset(token) => self.add_asset(token),
+                ConfigChange::RemoveAcceptedAsset(token) => {
+                    self.accepted_assets.retain(|accepted| *accepted != token);
+                }
+                ConfigChange::SetMaxTip(max_tip) => self.max_tip = max_tip,
+            }
+            Ok(())
+        }
+
+        // Pots
+
+        /// Sign off a spend drawing from a pot whose policy requires steward approval
+        #[ink(message)]
+        pub fn approve_pot_spend(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            let name = proposal.spend
+                .and_then(|spend| spend.pot)
+                .ok_or(Error::InvalidSpend)?;
+            let pot = self.pots.get(&name).ok_or(Error::PotNotFound)?;
+            
+            if !pot.stewards.contains(&caller) {
+                return Err(Error::NotAuthorized);
+            }
+            
+            self.pot_spend_approvals.insert(proposal_id, &caller);
+            Ok(())
+        }
+
+        /// Internal helper to check a pot's steward signed off a spend when its policy requires it
+        fn ensure_pot_spend_approved(&self, proposal_id: u32, spend: &TreasurySpend) -> Result<()> {
+            let Some(name) = &spend.pot else {
+                return Ok(());
+            };
+            
+            let pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+            if pot.policy.require_steward_approval && !self.pot_spend_approvals.contains(proposal_id) {
+                return Err(Error::PotSpendNotApproved);
+            }
+            Ok(())
+        }
+
+        /// Internal helper to apply a pot change from an executed proposal
+        fn apply_pot_action(&mut self, action: PotAction) -> Result<()> {
+            match action {
+                PotAction::CreatePot { name, policy, stewards } => {
+                    if self.pots.contains(&name) {
+                        return Err(Error::PotAlreadyExists);
+                    }
+                    let pot = Pot {
+                        name: name.clone(),
+                        balance: 0,
+                        liabilities: 0,
+                        policy,
+                        stewards,
+                    };
+                    self.pots.insert(&name, &pot);
+                    self.pot_names.push(name);
+                }
+                PotAction::UpdatePot { name, policy, stewards } => {
+                    let mut pot = self.pots.get(&name).ok_or(Error::PotNotFound)?;
+                    pot.policy = policy;
+                    pot.stewards = stewards;
+                    self.pots.insert(&name, &pot);
+                }
+                PotAction::Transfer { from, to, amount } => {
+                    // Only funds not promised to approved spends can move
+                    match &from {
+                        Some(name) => {
+                            let mut pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                            if amount > pot.balance.saturating_sub(pot.liabilities) {
+                                return Err(Error::InsufficientPotBalance);
+                            }
+                            pot.balance -= amount;
+                            self.pots.insert(name, &pot);
+                        }
+                        None => {
+                            if amount > self.get_available_balance() {
+                                return Err(Error::InsufficientTreasuryBalance);
+                            }
+                        }
+                    }
+                    if let Some(name) = &to {
+                        let mut pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                        pot.balance = pot.balance.checked_add(amount)
+                            .ok_or(Error::ArithmeticOverflow)?;
+                        self.pots.insert(name, &pot);
+                    }
+                    
+                    // Keep the pot total in step with funds entering or leaving the pots
+                    if from.is_none() {
+                        self.pots_total = self.pots_total.checked_add(amount)
+                            .ok_or(Error::ArithmeticOverflow)?;
+                    }
+                    if to.is_none() {
+                        self.pots_total = self.pots_total.saturating_sub(amount);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        // Grants
+
+        /// Submit evidence that a grant milestone has been delivered
+        #[ink(message)]
+        pub fn submit_milestone(&mut self, proposal_id: u32, milestone_index: u32, evidence: Hash) -> Result<()> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let mut grant = self.grants.get(proposal_id)
+                .ok_or(Error::GrantNotFound)?;
+            
+            if caller != grant.beneficiary {
+                return Err(Error::NotAuthorized);
+            }
+            if self.env().block_number() > grant.deadline {
+                return Err(Error::InvalidMilestone);
+            }
+            
+            let milestone = grant.milestones.get_mut(milestone_index as usize)
+                .ok_or(Error::InvalidMilestone)?;
+            if milestone.status != MilestoneStatus::Pending {
+                return Err(Error::InvalidMilestone);
+            }
+            milestone.status = MilestoneStatus::Submitted;
+            milestone.evidence = Some(evidence);
+            self.grants.insert(proposal_id, &grant);
+            
+            Ok(())
+        }
+
+        /// Review submitted milestone evidence as one of the grant's reviewers.
+        /// The milestone is released into a claim once enough reviewers approve, and fails
+        /// back to the treasury once approval can no longer be reached.
+        #[ink(message)]
+        pub fn review_milestone(&mut self, proposal_id: u32, milestone_index: u32, approve: bool) -> Result<MilestoneStatus> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let mut grant = self.grants.get(proposal_id)
+                .ok_or(Error::GrantNotFound)?;
+            
+            if !grant.reviewers.contains(&caller) {
+                return Err(Error::NotAuthorized);
+            }
+            
+            let reviewer_count = grant.reviewers.len();
+            let approvals_required = grant.approvals_required as usize;
+            let milestone = grant.milestones.get_mut(milestone_index as usize)
+                .ok_or(Error::InvalidMilestone)?;
+            if milestone.status != MilestoneStatus::Submitted {
+                return Err(Error::InvalidMilestone);
+            }
+            if milestone.approvals.contains(&caller) || milestone.rejections.contains(&caller) {
+                return Err(Error::AlreadyVoted);
+            }
+            
+            if approve {
+                milestone.approvals.push(caller);
+            } else {
+                milestone.rejections.push(caller);
+            }
+            
+            let amount = milestone.milestone.amount;
+            if milestone.approvals.len() >= approvals_required {
+                milestone.status = MilestoneStatus::Released;
+                self.claim_from_escrow(PayoutSource::Proposal(proposal_id), grant.beneficiary, amount, self.env().block_number())?;
+            } else if reviewer_count.saturating_sub(milestone.rejections.len()) < approvals_required {
+                milestone.status = MilestoneStatus::Failed;
+                self.escrowed_funds = self.escrowed_funds.saturating_sub(amount);
+            }
+            let status = milestone.status.clone();
+            self.grants.insert(proposal_id, &grant);
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(status)
+        }
+
+        /// Return every undelivered milestone of a grant to the treasury once its deadline has passed
+        #[ink(message)]
+        pub fn close_grant(&mut self, proposal_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let mut grant = self.grants.get(proposal_id)
+                .ok_or(Error::GrantNotFound)?;
+            if self.env().block_number() <= grant.deadline {
+                return Err(Error::GrantStillOpen);
+            }
+            
+            let mut returned: Balance = 0;
+            for milestone in &mut grant.milestones {
+                if matches!(milestone.status, MilestoneStatus::Pending | MilestoneStatus::Submitted) {
+                    milestone.status = MilestoneStatus::Failed;
+                    returned = returned.checked_add(milestone.milestone.amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+            }
+            self.escrowed_funds = self.escrowed_funds.saturating_sub(returned);
+            self.grants.insert(proposal_id, &grant);
+            
+            Ok(returned)
+        }
+
+        /// Internal helper to escrow an executed grant proposal
+        fn escrow_grant(&mut self, proposal_id: u32, terms: GrantTerms, amount: Balance, current_block: u32) -> Result<()> {
+            if self.get_treasury_balance() < self.escrowed_funds.saturating_add(amount) {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            
+            let grant = Grant {
+                proposal_id,
+                beneficiary: terms.beneficiary,
+                reviewers: terms.reviewers,
+                approvals_required: terms.approvals_required,
+                milestones: terms.milestones
+                    .into_iter()
+                    .map(|milestone| MilestoneState {
+                        milestone,
+                        status: MilestoneStatus::Pending,
+                        evidence: None,
+                        approvals: Vec::new(),
+                        rejections: Vec::new(),
+                    })
+                    .collect(),
+                deadline: current_block.checked_add(terms.completion_blocks)
+                    .ok_or(Error::ArithmeticOverflow)?,
+            };
+            self.escrowed_funds = self.escrowed_funds.checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.grants.insert(proposal_id, &grant);
+            
+            Ok(())
+        }
+
+        // Bounties
+
+        /// Split part of a bounty's unallocated value into a child bounty (curator only)
+        #[ink(message)]
+        pub fn add_child_bounty(&mut self, bounty_id: u32, description: String, value: Balance) -> Result<u32> {
+            self.migrate_storage()?;
+            let mut bounty = self.active_bounty(bounty_id)?;
+            if self.env().caller() != bounty.curator {
+                return Err(Error::NotAuthorized);
+            }
+            if value == 0 || value > bounty.unallocated || bounty.children.len() >= 10 {
+                return Err(Error::InvalidBounty);
+            }
+            
+            bounty.unallocated -= value;
+            bounty.children.push(ChildBounty {
+                description,
+                value,
+                status: ChildBountyStatus::Open,
+                beneficiary: None,
+                awarded_at: None,
+            });
+            let index = (bounty.children.len() - 1) as u32;
+            self.bounties.insert(bounty_id, &bounty);
+            
+            Ok(index)
+        }
+
+        /// Award an open child bounty to its beneficiary (curator only)
+        #[ink(message)]
+        pub fn award_child_bounty(&mut self, bounty_id: u32, index: u32, beneficiary: H160) -> Result<()> {
+            self.migrate_storage()?;
+            let mut bounty = self.active_bounty(bounty_id)?;
+            if self.env().caller() != bounty.curator {
+                return Err(Error::NotAuthorized);
+            }
+            
+            let current_block = self.env().block_number();
+            let child = bounty.children.get_mut(index as usize)
+                .ok_or(Error::ChildBountyNotFound)?;
+            if child.status != ChildBountyStatus::Open {
+                return Err(Error::InvalidBounty);
+            }
+            
+            child.status = ChildBountyStatus::Awarded;
+            child.beneficiary = Some(beneficiary);
+            child.awarded_at = Some(current_block);
+            self.bounties.insert(bounty_id, &bounty);
+            
+            Ok(())
+        }
+
+        /// Open a claim for an awarded child bounty's beneficiary once `BOUNTY_PAYOUT_DELAY` blocks have passed
+        #[ink(message)]
+        pub fn claim_child_bounty(&mut self, bounty_id: u32, index: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let mut bounty = self.bounties.get(bounty_id)
+                .ok_or(Error::BountyNotFound)?;
+            let current_block = self.env().block_number();
+            
+            let child = bounty.children.get_mut(index as usize)
+                .ok_or(Error::ChildBountyNotFound)?;
+            let (Some(beneficiary), Some(awarded_at)) = (child.beneficiary, child.awarded_at) else {
+                return Err(Error::PayoutNotReady);
+            };
+            if child.status != ChildBountyStatus::Awarded
+                || current_block < awarded_at.saturating_add(self.scaled_blocks(BOUNTY_PAYOUT_DELAY))
+            {
+                return Err(Error::PayoutNotReady);
+            }
+            
+            child.status = ChildBountyStatus::Claimed;
+            let value = child.value;
+            self.bounties.insert(bounty_id, &bounty);
+            self.claim_from_escrow(PayoutSource::Proposal(bounty_id), beneficiary, value, current_block)
+        }
+
+        /// Open a claim for the curator's fee once the whole bounty has been split and awarded (curator only)
+        #[ink(message)]
+        pub fn claim_curator_fee(&mut self, bounty_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let mut bounty = self.bounties.get(bounty_id)
+                .ok_or(Error::BountyNotFound)?;
+            if self.env().caller() != bounty.curator {
+                return Err(Error::NotAuthorized);
+            }
+            if bounty.fee_paid
+                || bounty.unallocated > 0
+                || bounty.children.iter().any(|child| child.status == ChildBountyStatus::Open)
+            {
+                return Err(Error::PayoutNotReady);
+            }
+            
+            bounty.fee_paid = true;
+            self.bounties.insert(bounty_id, &bounty);
+            self.claim_from_escrow(
+                PayoutSource::Proposal(bounty_id),
+                bounty.curator,
+                bounty.curator_fee,
+                self.env().block_number(),
+            )
+        }
+
+        /// Return everything not yet awarded, including an unearned curator fee, to the treasury
+        /// once the bounty has expired. Awarded child bounties stay claimable.
+        #[ink(message)]
+        pub fn expire_bounty(&mut self, bounty_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let mut bounty = self.bounties.get(bounty_id)
+                .ok_or(Error::BountyNotFound)?;
+            if bounty.expired {
+                return Err(Error::BountyExpired);
+            }
+            if self.env().block_number() <= bounty.expires_at {
+                return Err(Error::BountyStillOpen);
+            }
+            
+            let mut returned = bounty.unallocated;
+            for child in &mut bounty.children {
+                if child.status == ChildBountyStatus::Open {
+                    child.status = ChildBountyStatus::Expired;
+                    returned = returned.checked_add(child.value)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+            }
+            if !bounty.fee_paid {
+                bounty.fee_paid = true;
+                returned = returned.checked_add(bounty.curator_fee)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            
+            bounty.unallocated = 0;
+            bounty.expired = true;
+            self.escrowed_funds = self.escrowed_funds.saturating_sub(returned);
+            self.bounties.insert(bounty_id, &bounty);
+            
+            Ok(returned)
+        }
+
+        /// Internal helper to load a bounty the curator can still work on
+        fn active_bounty(&self, bounty_id: u32) -> Result<Bounty> {
+            let bounty = self.bounties.get(bounty_id)
+                .ok_or(Error::BountyNotFound)?;
+            if bounty.expired || self.env().block_number() > bounty.expires_at {
+                return Err(Error::BountyExpired);
+            }
+            Ok(bounty)
+        }
+
+        /// Internal helper to escrow an executed bounty proposal
+        fn fund_bounty(&mut self, proposal_id: u32, terms: BountyTerms, amount: Balance, current_block: u32) -> Result<()> {
+            if self.get_treasury_balance() < self.escrowed_funds.saturating_add(amount) {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            
+            let bounty = Bounty {
+                proposal_id,
+                description: terms.description,
+                curator: terms.curator,
+                curator_fee: terms.curator_fee,
+                fee_paid: false,
+                unallocated: amount.saturating_sub(terms.curator_fee),
+                children: Vec::new(),
+                expires_at: current_block.checked_add(terms.duration_blocks)
+                    .ok_or(Error::ArithmeticOverflow)?,
+                expired: false,
+            };
+            self.escrowed_funds = self.escrowed_funds.checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.bounties.insert(proposal_id, &bounty);
+            
+            Ok(())
+        }
+
+        // Tips
+
+        /// Nominate a beneficiary for a tip, with a hash of the reason (registered voters only)
+        #[ink(message)]
+        pub fn report_tip(&mut self, reason: Hash, beneficiary: H160) -> Result<u32> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            if !self.registered_voters.get(caller).unwrap_or(false) {
+                return Err(Error::NotRegisteredVoter);
+            }
+            
+            let tip_id = self.next_tip_id;
+            self.next_tip_id = self.next_tip_id.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.open_tips = self.open_tips.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.tips.insert(tip_id, &Tip {
+                id: tip_id,
+                reason,
+                beneficiary,
+                finder: caller,
+                tips: Vec::new(),
+                closes_at: None,
+                paid: None,
+            });
+            
+            Ok(tip_id)
+        }
+
+        /// Propose an amount up to `max_tip` for a tip, replacing any earlier amount (council only).
+        /// The countdown starts once a majority of the council has tipped.
+        #[ink(message)]
+        pub fn tip(&mut self, tip_id: u32, amount: Balance) -> Result<()> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            if !self.council_members.contains(&caller) {
+                return Err(Error::NotCouncilMember);
+            }
+            
+            let mut tip = self.tips.get(tip_id)
+                .ok_or(Error::TipNotFound)?;
+            if tip.paid.is_some() {
+                return Err(Error::TipClosed);
+            }
+            if amount > self.max_tip {
+                return Err(Error::TipTooLarge);
+            }
+            
+            match tip.tips.iter_mut().find(|(tipper, _)| *tipper == caller) {
+                Some(entry) => entry.1 = amount,
+                None => tip.tips.push((caller, amount)),
+            }
+            
+            let threshold = self.council_members.len() / 2 + 1;
+            if tip.closes_at.is_none() && tip.tips.len() >= threshold {
+                tip.closes_at = Some(self.env().block_number().checked_add(self.scaled_blocks(TIP_COUNTDOWN))
+                    .ok_or(Error::ArithmeticOverflow)?);
+            }
+            self.tips.insert(tip_id, &tip);
+            
+            Ok(())
+        }
+
+        /// Open a claim for the median of the tipped amounts once the countdown has elapsed.
+        /// Outflow limits apply; a breach pays nothing.
+        #[ink(message)]
+        pub fn close_tip(&mut self, tip_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let current_block = self.env().block_number();
+            let mut tip = self.tips.get(tip_id)
+                .ok_or(Error::TipNotFound)?;
+            if tip.paid.is_some() {
+                return Err(Error::TipClosed);
+            }
+            if tip.closes_at.is_none_or(|closes_at| current_block < closes_at) {
+                return Err(Error::PayoutNotReady);
+            }
+            if self.outflow_paused {
+                return Err(Error::TreasuryPaused);
+            }
+            
+            let mut amounts: Vec<Balance> = tip.tips.iter().map(|(_, amount)| *amount).collect();
+            amounts.sort_unstable();
+            let median = amounts[amounts.len() / 2];
+            if median > self.get_available_balance() {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            if !self.outflow_within_limits(&Asset::Native, &ProposalType::Treasury, median, current_block)? {
+                return Err(Error::OutflowLimitExceeded);
+            }
+            self.record_outflow(&Asset::Native, &ProposalType::Treasury, median, current_block);
+            
+            tip.paid = Some(median);
+            self.open_tips = self.open_tips.saturating_sub(1);
+            self.tips.insert(tip_id, &tip);
+            self.open_claim(PayoutSource::Tip(tip_id), tip.beneficiary, median, &Asset::Native, current_block)?;
+            
+            Ok(median)
+        }
+
+        // Claims
+
+        /// Withdraw an open claim (beneficiary only)
+        #[ink(message)]
+        pub fn claim(&mut self, claim_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let claim = self.claims.get(claim_id)
+                .ok_or(Error::ClaimNotFound)?;
+            if self.env().caller() != claim.beneficiary {
+                return Err(Error::NotAuthorized);
+            }
+            if self.env().block_number() > claim.expires_at {
+                return Err(Error::ClaimExpired);
+            }
+            
+            self.close_claim(&claim);
+            self.pay_spend(claim.beneficiary, claim.amount, &claim.asset)?;
+            
+            Ok(claim.amount)
+        }
+
+        /// Return an expired, unclaimed spend to the treasury
+        #[ink(message)]
+        pub fn expire_claim(&mut self, claim_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let claim = self.claims.get(claim_id)
+                .ok_or(Error::ClaimNotFound)?;
+            if self.env().block_number() <= claim.expires_at {
+                return Err(Error::ClaimStillOpen);
+            }
+            
+            self.close_claim(&claim);
+            Ok(claim.amount)
+        }
+
+        /// Internal helper to hold an executed spend for its recipients to withdraw, one claim per share.
+        /// Native funds are escrowed; token funds stay reserved as token liabilities.
+        fn open_spend_claims(&mut self, proposal_id: u32, spend: &TreasurySpend, current_block: u32) -> Result<()> {
+            for (beneficiary, amount) in Self::split_spend(spend)? {
+                self.open_claim(PayoutSource::Proposal(proposal_id), beneficiary, amount, &spend.asset, current_block)?;
+            }
+            Ok(())
+        }
+
+        /// Internal helper holding funds for a single claim
+        fn open_claim(
+            &mut self,
+            source: PayoutSource,
+            beneficiary: H160,
+            amount: Balance,
+            asset: &Asset,
+            current_block: u32,
+        ) -> Result<()> {
+            match *asset {
+                Asset::Native => {
+                    self.escrowed_funds = self.escrowed_funds.checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+                Asset::Psp22(token) => {
+                    let liabilities = self.asset_liabilities.get(token).unwrap_or(0)
+                        .checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    self.asset_liabilities.insert(token, &liabilities);
+                }
+            }
+            
+            let expires_at = current_block.checked_add(self.scaled_blocks(CLAIM_PERIOD))
+                .ok_or(Error::ArithmeticOverflow)?;
+            let claim_id = self.next_claim_id;
+            self.next_claim_id = self.next_claim_id.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.claims.insert(claim_id, &Claim {
+                id: claim_id,
+                source,
+                beneficiary,
+                amount,
+                asset: asset.clone(),
+                expires_at,
+            });
+            let mut open_claims = self.beneficiary_claims.get(beneficiary).unwrap_or_default();
+            open_claims.push(claim_id);
+            self.beneficiary_claims.insert(beneficiary, &open_claims);
+            
+            Ok(())
+        }
+
+        /// Internal helper handing native funds already held in escrow to a beneficiary as a claim
+        fn claim_from_escrow(&mut self, source: PayoutSource, beneficiary: H160, amount: Balance, current_block: u32) -> Result<()> {
+            self.escrowed_funds = self.escrowed_funds.saturating_sub(amount);
+            self.open_claim(source, beneficiary, amount, &Asset::Native, current_block)
+        }
+
+        /// Internal helper to resolve a spend into per-recipient amounts, checking the shares add up.
+        /// Shares are either all fixed amounts summing to the spend or all basis points summing to 10 000.
+        fn split_spend(spend: &TreasurySpend) -> Result<Vec<(H160, Balance)>> {
+            let splits = match &spend.recipient {
+                SpendRecipient::Single(beneficiary) => return Ok(vec![(*beneficiary, spend.amount)]),
+                SpendRecipient::Split(splits) => splits,
+            };
+            if splits.is_empty() || splits.len() > MAX_SPEND_SPLITS {
+                return Err(Error::InvalidSpend);
+            }
+            
+            let mut shares = Vec::new();
+            let mut allocated: Balance = 0;
+            let mut total_basis_points: u32 = 0;
+            for share in splits {
+                let amount = match (&splits[0].portion, &share.portion) {
+                    (SharePortion::Amount(_), SharePortion::Amount(amount)) => *amount,
+                    (SharePortion::BasisPoints(_), SharePortion::BasisPoints(points)) => {
+                        total_basis_points = total_basis_points.saturating_add(u32::from(*points));
+                        spend.amount.checked_mul(Balance::from(*points))
+                            .ok_or(Error::ArithmeticOverflow)?
+                            / 10_000
+                    }
+                    // Fixed amounts and basis points cannot be mixed
+                    _ => return Err(Error::InvalidSpend),
+                };
+                if amount == 0 {
+                    return Err(Error::InvalidSpend);
+                }
+                allocated = allocated.checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                shares.push((share.recipient, amount));
+            }
+            
+            match splits[0].portion {
+                SharePortion::Amount(_) if allocated != spend.amount => return Err(Error::InvalidSpend),
+                SharePortion::BasisPoints(_) if total_basis_points != 10_000 => return Err(Error::InvalidSpend),
+                _ => {}
+            }
+            if let Some(last) = shares.last_mut() {
+                last.1 = last.1.saturating_add(spend.amount.saturating_sub(allocated));
+            }
+            
+            Ok(shares)
+        }
+
+        /// Internal helper to remove a claim and release the funds held for it
+        fn close_claim(&mut self, claim: &Claim) {
+            match claim.asset {
+                Asset::Native => {
+                    self.escrowed_funds = self.escrowed_funds.saturating_sub(claim.amount);
+                }
+                Asset::Psp22(token) => {
+                    let liabilities = self.asset_liabilities.get(token).unwrap_or(0).saturating_sub(claim.amount);
+                    self.asset_liabilities.insert(token, &liabilities);
+                }
+            }
+            
+            self.claims.remove(claim.id);
+            let mut open_claims = self.beneficiary_claims.get(claim.beneficiary).unwrap_or_default();
+            open_claims.retain(|id| *id != claim.id);
+            self.beneficiary_claims.insert(claim.beneficiary, &open_claims);
+        }
+
+        // Assets
+
+        /// Internal helper to accept a PSP22 token
+        fn add_asset(&mut self, token: H160) {
+            if !self.accepted_assets.contains(&token) {
+                self.accepted_assets.push(token);
+            }
+        }
+
+        /// Deposit an accepted PSP22 token into the treasury.
+        /// The caller must first approve the treasury to spend `amount`.
+        #[ink(message)]
+        pub fn deposit_asset(&mut self, token: H160, amount: Balance) -> Result<()> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            if !self.accepted_assets.contains(&token) {
+                return Err(Error::AssetNotAccepted);
+            }
+            
+            let treasury = self.env().address();
+            self.psp22_transfer_from(token, caller, treasury, amount)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(())
+        }
+
+        // Outflow limits
+
+        /// Internal helper to validate and store outflow limits
+        fn apply_outflow_limits(&mut self, limits: Option<OutflowLimits>) -> Result<()> {
+            if limits.as_ref().is_some_and(|limits| limits.window_blocks == 0) {
+                return Err(Error::InvalidOutflowLimits);
+            }
+            
+            self.outflow_limits = limits;
+            Ok(())
+        }
+
+        /// Trip the circuit breaker, pausing all spends (guardians only)
+        #[ink(message)]
+        pub fn pause_outflows(&mut self) -> Result<()> {
+            self.migrate_storage()?;
+            if !self.guardians.contains(&self.env().caller()) {
+                return Err(Error::NotAuthorized);
+            }
+            
+            self.outflow_paused = true;
+            Ok(())
+        }
+
+        /// Pause outflows because paying a passed spend now would breach the outflow limits.
+        /// Such a breach fails `execute_proposal`, which cannot keep a pause, so anyone may report it.
+        #[ink(message)]
+        pub fn report_outflow_breach(&mut self, proposal_id: u32) -> Result<()> {
+            self.migrate_storage()?;
+            let current_block = self.env().block_number();
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if !matches!(proposal.status, ProposalStatus::Passed | ProposalStatus::Queued) {
+                return Err(Error::ProposalNotReadyForExecution);
+            }
+            let spend = self.committed_spend(&proposal)
+                .ok_or(Error::InvalidSpend)?;
+            if self.outflow_within_limits(&spend.asset, &proposal.proposal_type, spend.amount, current_block)? {
+                return Err(Error::OutflowWithinLimits);
+            }
+            
+            self.outflow_paused = true;
+            Ok(())
+        }
+
+        /// Internal helper checking whether an outflow fits the configured limits
+        fn outflow_within_limits(&self, asset: &Asset, proposal_type: &ProposalType, amount: Balance, current_block: u32) -> Result<bool> {
+            let Some(limits) = &self.outflow_limits else {
+                return Ok(true);
+            };
+            let window_start = current_block.saturating_sub(limits.window_blocks);
+            let window = self.recent_outflows.iter().filter(|record| record.block > window_start);
+            
+            // Token outflows are capped by their own budget, since their amounts are not comparable
+            if let Asset::Psp22(token) = asset {
+                let Some(budget) = limits.token_budgets.iter().find(|(listed, _)| listed == token).map(|(_, budget)| *budget) else {
+                    return Ok(false);
+                };
+                let mut token_total = amount;
+                for record in window.filter(|record| record.asset == *asset) {
+                    token_total = token_total.checked_add(record.amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+                return Ok(token_total <= budget);
+            }
+            
+            if limits.max_single_spend.is_some_and(|max| amount > max) {
+                return Ok(false);
+            }
+            
+            let mut window_total = amount;
+            let mut type_total = amount;
+            for record in window.filter(|record| record.asset == Asset::Native) {
+                window_total = window_total.checked_add(record.amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                if record.proposal_type == *proposal_type {
+                    type_total = type_total.checked_add(record.amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+            }
+            if limits.max_window_outflow.is_some_and(|max| window_total > max) {
+                return Ok(false);
+            }
+            let type_budget = limits.type_budgets
+                .iter()
+                .find(|(budget_type, _)| budget_type == proposal_type)
+                .map(|(_, budget)| *budget);
+            Ok(type_budget.is_none_or(|budget| type_total <= budget))
+        }
+
+        /// Internal helper recording a paid outflow against the rolling window, once it has been checked
+        fn record_outflow(&mut self, asset: &Asset, proposal_type: &ProposalType, amount: Balance, current_block: u32) {
+            let Some(limits) = &self.outflow_limits else {
+                return;
+            };
+            
+            // Drop outflows that have left the rolling window
+            let window_start = current_block.saturating_sub(limits.window_blocks);
+            self.recent_outflows.retain(|record| record.block > window_start);
+            self.recent_outflows.push(OutflowRecord {
+                block: current_block,
+                amount,
+                asset: asset.clone(),
+                proposal_type: proposal_type.clone(),
+            });
+        }
+
+        // Spend periods
+
+        /// Internal helper to validate and store the spend period configuration.
+        /// Spend periods can only be disabled once the approvals queue is empty.
+        fn apply_spend_period_config(&mut self, config: Option<SpendPeriodConfig>) -> Result<()> {
+            match &config {
+                Some(config) => {
+                    if config.period_blocks == 0 || config.burn_percent > 100 {
+                        return Err(Error::InvalidSpendPeriodConfig);
+                    }
+                    self.next_spend_period = self.env().block_number().checked_add(config.period_blocks)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+                None => {
+                    if !self.approvals_queue.is_empty() {
+                        return Err(Error::InvalidSpendPeriodConfig);
+                    }
+                    self.next_spend_period = 0;
+                }
+            }
+            self.spend_period_config = config;
+            
+            Ok(())
+        }
+
+        /// Close the current spend period: pay queued approvals the treasury can
+        /// cover, in queue order, then burn a share of the unspent available balance
+        #[ink(message)]
+        pub fn process_spend_period(&mut self) -> Result<SpendPeriodReport> {
+            self.migrate_storage()?;
+            let current_block = self.env().block_number();
+            let config = self.spend_period_config.clone()
+                .ok_or(Error::SpendPeriodsDisabled)?;
+            if current_block < self.next_spend_period {
+                return Err(Error::SpendPeriodNotReached);
+            }
+            if self.outflow_paused {
+                return Err(Error::TreasuryPaused);
+            }
+            
+            // Pay queued approvals, deferring those the treasury cannot cover yet
+            let mut paid_proposals = Vec::new();
+            let mut deferred_proposals = Vec::new();
+            let mut total_paid: Balance = 0;
+            for proposal_id in core::mem::take(&mut self.approvals_queue) {
+                let Some(mut proposal) = self.proposals.get(proposal_id) else {
+                    continue;
+                };
+                let Some(spend) = proposal.spend.clone() else {
+                    continue;
+                };
+                
+                // Escrowed grants and streams are only held in the native currency
+                let spendable = match (&spend.pot, &spend.asset) {
+                    (Some(name), _) => self.pots.get(name).map_or(0, |pot| pot.balance),
+                    (None, Asset::Native) => self.get_treasury_balance()
+                        .saturating_sub(self.escrowed_funds)
+                        .saturating_sub(self.pots_total)
+                        .saturating_sub(self.held_deposits),
+                    (None, Asset::Psp22(token)) => self.psp22_balance_of(*token)?,
+                };
+                // Spends the treasury cannot cover yet, or that would breach an outflow limit, wait
+                if spend.amount > spendable
+                    || !self.outflow_within_limits(&spend.asset, &proposal.proposal_type, spend.amount, current_block)?
+                {
+                    deferred_proposals.push(proposal_id);
+                    continue;
+                }
+                
+                self.record_outflow(&spend.asset, &proposal.proposal_type, spend.amount, current_block);
+                self.debit_pot(&spend)?;
+                self.open_spend_claims(proposal_id, &spend, current_block)?;
+                self.release_liability(&spend)?;
+                total_paid = total_paid.checked_add(spend.amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                proposal.status = ProposalStatus::Executed;
+                self.proposals.insert(proposal_id, &proposal);
+                paid_proposals.push(proposal_id);
+            }
+            self.approvals_queue = deferred_proposals.clone();
+            
+            // Burn a share of whatever is left unspent
+            let burned = self.get_available_balance()
+                .checked_mul(config.burn_percent as u128)
+                .ok_or(Error::ArithmeticOverflow)? / 100;
+            self.burned_funds = self.burned_funds.checked_add(burned)
+                .ok_or(Error::ArithmeticOverflow)?;
+            
+            // Record the report and move to the first boundary after the current block
+            let report = SpendPeriodReport {
+                period: self.spend_period_index,
+                processed_at: current_block,
+                paid_proposals,
+                total_paid,
+                burned,
+                deferred_proposals,
+            };
+            self.spend_reports.insert(self.spend_period_index, &report);
+            self.spend_period_index = self.spend_period_index.checked_add(1)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let periods_elapsed = (current_block - self.next_spend_period) / config.period_blocks + 1;
+            self.next_spend_period = periods_elapsed.checked_mul(config.period_blocks)
+                .and_then(|blocks| self.next_spend_period.checked_add(blocks))
+                .ok_or(Error::ArithmeticOverflow)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(report)
+        }
+
+        // Streams
+
+        /// Move the vested but unclaimed part of a stream into a claim
+        #[ink(message)]
+        pub fn claim_stream(&mut self, stream_id: u32) -> Result<Balance> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            let mut stream = self.streams.get(stream_id)
+                .ok_or(Error::StreamNotFound)?;
+            
+            if caller != stream.beneficiary {
+                return Err(Error::NotAuthorized);
+            }
+            
+            let claimable = Self::vested_amount(&stream, current_block)?.saturating_sub(stream.claimed);
+            if claimable == 0 {
+                return Err(Error::NothingToClaim);
+            }
+            
+            stream.claimed = stream.claimed.checked_add(claimable)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.streams.insert(stream_id, &stream);
+            self.claim_from_escrow(PayoutSource::Proposal(stream_id), stream.beneficiary, claimable, current_block)?;
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(claimable)
+        }
+
+        /// Internal helper to escrow an executed spend as a stream
+        fn open_stream(&mut self, proposal_id: u32, spend: &TreasurySpend, schedule: PayoutSchedule, current_block: u32) -> Result<()> {
+            let SpendRecipient::Single(beneficiary) = spend.recipient else {
+                return Err(Error::InvalidSpend);
+            };
+            if self.get_treasury_balance() < self.escrowed_funds.saturating_add(spend.amount) {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            
+            let (cliff, duration) = match schedule {
+                PayoutSchedule::Linear { duration } => (0, duration),
+                PayoutSchedule::Cliff { cliff, duration } => (cliff, duration),
+            };
+            let stream = Stream {
+                proposal_id,
+                beneficiary,
+                total: spend.amount,
+                claimed: 0,
+                start_block: current_block,
+                cliff_block: current_block.checked_add(cliff)
+                    .ok_or(Error::ArithmeticOverflow)?,
+                end_block: current_block.checked_add(duration)
+                    .ok_or(Error::ArithmeticOverflow)?,
+                cancelled_at: None,
+            };
+            self.escrowed_funds = self.escrowed_funds.checked_add(spend.amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.streams.insert(proposal_id, &stream);
+            
+            Ok(())
+        }
+
+        /// Internal helper to stop a stream vesting and return its unvested remainder to the treasury
+        fn cancel_stream(&mut self, stream_id: u32, current_block: u32) -> Result<()> {
+            let mut stream = self.streams.get(stream_id)
+                .ok_or(Error::StreamNotFound)?;
+            if stream.cancelled_at.is_some() {
+                return Err(Error::StreamCancelled);
+            }
+            
+            let unvested = stream.total.saturating_sub(Self::vested_amount(&stream, current_block)?);
+            stream.cancelled_at = Some(current_block);
+            self.escrowed_funds = self.escrowed_funds.saturating_sub(unvested);
+            self.streams.insert(stream_id, &stream);
+            
+            Ok(())
+        }
+
+        /// Internal helper returning how much of a stream has vested at a block
+        fn vested_amount(stream: &Stream, block: u32) -> Result<Balance> {
+            // Vesting stops when a stream is cancelled
+            let block = stream.cancelled_at.map_or(block, |cancelled_at| block.min(cancelled_at));
+            
+            if block < stream.cliff_block {
+                return Ok(0);
+            }
+            if block >= stream.end_block {
+                return Ok(stream.total);
+            }
+            
+            let elapsed = (block - stream.start_block) as u128;
+            let duration = (stream.end_block - stream.start_block) as u128;
+            stream.total.checked_mul(elapsed)
+                .map(|vested| vested / duration)
+                .ok_or(Error::ArithmeticOverflow)
+        }
+
+        // Council
+
+        /// Vote as a council member on a veto or fast-track motion for a proposal
+        #[ink(message)]
+        pub fn council_vote(&mut self, proposal_id: u32, kind: CouncilMotionKind, approve: bool) -> Result<VoteOutcome> {
+            self.migrate_storage()?;
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            
+            if !self.council_members.contains(&caller) {
+                return Err(Error::NotCouncilMember);
+            }
+            let settled = self.settle_before_vote(proposal_id)?;
+            
+            // Settling can make a veto applicable; otherwise the settled status is the result
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if let Err(error) = Self::ensure_motion_applicable(&proposal, &kind, self.clock_now(&proposal.clock)) {
+                return settled.map(VoteOutcome::VotingClosed).ok_or(error);
+            }
+            
+            let mut motion = self.council_motions.get(&(proposal_id, kind.clone()))
+                .unwrap_or(CouncilMotion {
+                    proposal_id,
+                    kind: kind.clone(),
+                    ayes: Vec::new(),
+                    nays: Vec::new(),
+                    opened_at: current_block,
+                });
+            if motion.ayes.contains(&caller) || motion.nays.contains(&caller) {
+                return Err(Error::AlreadyVoted);
+            }
+            
+            if approve {
+                motion.ayes.push(caller);
+            } else {
+                motion.nays.push(caller);
+            }
+            self.council_motions.insert(&(proposal_id, kind), &motion);
+            
+            Ok(VoteOutcome::Cast)
+        }
+
+        /// Close a council motion, applying it if it reached its threshold.
+        /// Once the motion has been open for `COUNCIL_MOTION_DURATION` blocks,
+        /// members who abstained are counted with the prime member's vote.
+        /// Returns whether the motion was approved.
+        #[ink(message)]
+        pub fn close_council_motion(&mut self, proposal_id: u32, kind: CouncilMotionKind) -> Result<bool> {
+            self.migrate_storage()?;
+            self.finalize_proposal(proposal_id)?;
+            
+            let caller = self.env().caller();
+            let current_block = self.env().block_number();
+            
+            if !self.council_members.contains(&caller) {
+                return Err(Error::NotCouncilMember);
+            }
+            
+            let motion = self.council_motions.get(&(proposal_id, kind.clone()))
+                .ok_or(Error::CouncilMotionNotFound)?;
+            let mut proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            Self::ensure_motion_applicable(&proposal, &kind, self.clock_now(&proposal.clock))?;
+            
+            // Only members still on the council count towards the tally
+            let members = self.council_members.len() as u32;
+            let mut ayes = motion.ayes.iter().filter(|member| self.council_members.contains(member)).count() as u32;
+            let motion_ended = current_block >= motion.opened_at.saturating_add(self.scaled_blocks(COUNCIL_MOTION_DURATION));
+            if motion_ended {
+                if let Some(prime) = self.council_prime {
+                    if motion.ayes.contains(&prime) {
+                        let abstentions = self.council_members
+                            .iter()
+                            .filter(|member| !motion.ayes.contains(member) && !motion.nays.contains(member))
+                            .count() as u32;
+                        ayes = ayes.checked_add(abstentions).ok_or(Error::ArithmeticOverflow)?;
+                    }
+                }
+            }
+            
+            // Vetoes need a simple majority, fast-tracking a two-thirds supermajority
+            let approved = match kind {
+                CouncilMotionKind::Veto => ayes as u64 * 2 > members as u64,
+                CouncilMotionKind::FastTrack => ayes as u64 * 3 >= members as u64 * 2,
+            };
+            if !approved && !motion_ended {
+                return Err(Error::CouncilMotionNotReady);
+            }
+            
+            if approved {
+                match kind {
+                    CouncilMotionKind::Veto => {
+                        // Release the funds reserved for a vetoed spend
+                        if let Some(spend) = self.committed_spend(&proposal) {
+                            self.release_liability(&spend)?;
+                        }
+                        proposal.status = ProposalStatus::Vetoed;
+                    }
+                    CouncilMotionKind::FastTrack => {
+                        let now = self.clock_now(&proposal.clock);
+                        proposal.voting_end = now;
+                        proposal.execution_time = now;
+                    }
+                }
+                self.proposals.insert(proposal_id, &proposal);
+            }
+            self.council_motions.remove(&(proposal_id, kind));
+            
+            // Event emission removed due to ink! v6 alpha bugs
+            
+            Ok(approved)
+        }
+
+        /// Internal helper to check a council motion can still act on a proposal
+        fn ensure_motion_applicable(proposal: &Proposal, kind: &CouncilMotionKind, now: u64) -> Result<()> {
+            match kind {
+                CouncilMotionKind::Veto => {
+                    if proposal.proposal_type != ProposalType::Treasury
+                        || proposal.status != ProposalStatus::Passed
+                        || now >= proposal.execution_time
+                    {
+                        return Err(Error::NotVetoable);
+                    }
+                }
+                CouncilMotionKind::FastTrack => {
+                    if !matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Confirming) {
+                        return Err(Error::ProposalNotActive);
+                    }
+                    // Fast-tracking would skip the mandatory upgrade delay
+                    if proposal.code_upgrade.is_some() {
+                        return Err(Error::UpgradeDelayTooShort);
+                    }
+                    if now > proposal.voting_end {
+                        return Err(Error::VotingPeriodEnded);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Internal helper to apply a council change from an executed proposal
+        fn apply_council_change(&mut self, proposal: &Proposal, change: CouncilChange) -> Result<()> {
+            match change {
+                CouncilChange::ElectMembers(candidates) => {
+                    // Seat the elected candidates in the order their seats were filled
+                    let winners = self.election_winners.get(proposal.id).unwrap_or_default();
+                    self.council_members = winners
+                        .into_iter()
+                        .filter_map(|index| candidates.get(index as usize).copied())
+                        .collect();
+                }
+                change => {
+                    // Other changes only apply when "Aye" won
+                    if !self.approves_action(proposal) {
+                        return Ok(());
+                    }
+                    match change {
+                        CouncilChange::SetMembers(members) => self.council_members = members,
+                        CouncilChange::AddMember(member) => {
+                            if !self.council_members.contains(&member) {
+                                self.council_members.push(member);
+                            }
+                        }
+                        CouncilChange::RemoveMember(member) => {
+                            self.council_members.retain(|existing| *existing != member);
+                        }
+                        CouncilChange::SetPrime(prime) => self.council_prime = prime,
+                        CouncilChange::ElectMembers(_) => {}
+                    }
+                }
+            }
+            
+            // The prime must remain a council member
+            if self.council_prime.is_some_and(|prime| !self.council_members.contains(&prime)) {
+                self.council_prime = None;
+            }
+            
+            Ok(())
+        }
+
+        /// Internal helper to check whether "Aye", the first option, won a proposal carrying an action
+        fn approves_action(&self, proposal: &Proposal) -> bool {
+            self.resolve_winner(proposal) == Some(0)
+        }
+
+        /// Internal helper checking whether a proposal carries an action applied when "Aye" wins
+        fn carries_action(proposal: &Proposal) -> bool {
+            proposal.spend.is_some()
+                || proposal.council_change.is_some()
+                || proposal.cancel_stream.is_some()
+                || proposal.pot_action.is_some()
+                || !proposal.config_changes.is_empty()
+                || proposal.code_upgrade.is_some()
+        }
+
+        /// Internal helper returning the spend a proposal commits the treasury to pay
+        fn committed_spend(&self, proposal: &Proposal) -> Option<TreasurySpend> {
+            let spend = proposal.spend.as_ref()?;
+            self.approves_action(proposal).then(|| spend.clone())
+        }
+
+        /// Internal helper to reserve funds for an approved spend
+        fn add_liability(&mut self, spend: &TreasurySpend) -> Result<()> {
+            let amount = spend.amount;
+            if let Some(name) = &spend.pot {
+                let mut pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                pot.liabilities = pot.liabilities.checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.pots.insert(name, &pot);
+                return Ok(());
+            }
+            
+            match &spend.asset {
+                Asset::Native => {
+                    self.approved_liabilities = self.approved_liabilities.checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                }
+                Asset::Psp22(token) => {
+                    let liabilities = self.asset_liabilities.get(token).unwrap_or(0)
+                        .checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    self.asset_liabilities.insert(token, &liabilities);
+                }
+            }
+            Ok(())
+        }
+
+        /// Internal helper to release funds reserved for a spend that was paid, escrowed or vetoed
+        fn release_liability(&mut self, spend: &TreasurySpend) -> Result<()> {
+            let amount = spend.amount;
+            if let Some(name) = &spend.pot {
+                let mut pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                pot.liabilities = pot.liabilities.saturating_sub(amount);
+                self.pots.insert(name, &pot);
+                return Ok(());
+            }
+            
+            match &spend.asset {
+                Asset::Native => {
+                    self.approved_liabilities = self.approved_liabilities.saturating_sub(amount);
+                }
+                Asset::Psp22(token) => {
+                    let liabilities = self.asset_liabilities.get(token).unwrap_or(0).saturating_sub(amount);
+                    self.asset_liabilities.insert(token, &liabilities);
+                }
+            }
+            Ok(())
+        }
+
+        /// Internal helper to take the funds of a pot spend out of its pot
+        fn debit_pot(&mut self, spend: &TreasurySpend) -> Result<()> {
+            let Some(name) = &spend.pot else {
+                return Ok(());
+            };
+            
+            let mut pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+            pot.balance = pot.balance.checked_sub(spend.amount)
+                .ok_or(Error::InsufficientPotBalance)?;
+            self.pots_total = self.pots_total.saturating_sub(spend.amount);
+            self.pots.insert(name, &pot);
+            Ok(())
+        }
+
+        /// Internal helper returning the balance of an asset not promised to approved spends
+        fn available_balance_of(&self, asset: &Asset) -> Result<Balance> {
+            match asset {
+                Asset::Native => Ok(self.get_available_balance()),
+                Asset::Psp22(token) => Ok(self.psp22_balance_of(*token)?
+                    .saturating_sub(self.asset_liabilities.get(token).unwrap_or(0))),
+            }
+        }
+
+        /// Internal helper checking a spend against funds not already promised elsewhere
+        fn ensure_spend_affordable(&self, spend: &TreasurySpend) -> Result<()> {
+            if let Some(name) = &spend.pot {
+                let pot = self.pots.get(name).ok_or(Error::PotNotFound)?;
+                if spend.amount > pot.balance.saturating_sub(pot.liabilities) {
+                    return Err(Error::InsufficientPotBalance);
+                }
+            } else if spend.amount > self.available_balance_of(&spend.asset)? {
+                return Err(Error::InsufficientTreasuryBalance);
+            }
+            Ok(())
+        }
+
+        /// Internal helper to transfer a payout to its beneficiary
+        fn pay_spend(&mut self, beneficiary: H160, amount: Balance, asset: &Asset) -> Result<()> {
+            match *asset {
+                Asset::Native => {
+                    if self.env().balance() < U256::from(amount) {
+                        return Err(Error::InsufficientTreasuryBalance);
+                    }
+                    self.env().transfer(beneficiary, U256::from(amount))
+                        .map_err(|_| Error::TransferFailed)
+                }
+                Asset::Psp22(token) => self.psp22_transfer(token, beneficiary, amount),
+            }
+        }
+
+        /// Internal helper calling `PSP22::balance_of` for the treasury on a token contract
+        fn psp22_balance_of(&self, token: H160) -> Result<Balance> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::balance_of")))
+                        .push_arg(self.env().address()),
+                )
+                .returns::<Balance>()
+                .try_invoke()
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)
+        }
+
+        /// Internal helper calling `PSP22::transfer` on a token contract
+        fn psp22_transfer(&mut self, token: H160, to: H160, value: Balance) -> Result<()> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<core::result::Result<(), Psp22Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)
+        }
+
+        /// Internal helper calling `PSP22::transfer_from` on a token contract
+        fn psp22_transfer_from(&mut self, token: H160, from: H160, to: H160, value: Balance) -> Result<()> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<core::result::Result<(), Psp22Error>>()
+                .try_invoke()
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)?
+                .map_err(|_| Error::TokenTransferFailed)
+        }
+
+        /// Internal helper returning the indices of the options sharing the highest vote count
+        fn leading_options(proposal: &Proposal) -> Vec<usize> {
+            let mut max_votes = 0u128;
+            let mut leading_options = Vec::new();
+            
+            for (index, &votes) in proposal.vote_counts.iter().enumerate() {
+                if votes > max_votes {
+                    max_votes = votes;
+                    leading_options.clear();
+                    leading_options.push(index);
+                } else if votes == max_votes && votes > 0 {
+                    leading_options.push(index);
+                }
+            }
+            
+            leading_options
+        }
+
+        /// Internal helper to pick the winning option, settling ties with the proposal's tie policy
+        fn resolve_winner(&self, proposal: &Proposal) -> Option<usize> {
+            let leading_options = Self::leading_options(proposal);
+            if leading_options.len() <= 1 {
+                return leading_options.first().copied();
+            }
+            
+            match proposal.governance_params.tie_policy {
+                TiePolicy::Reject | TiePolicy::Runoff => None,
+                TiePolicy::EarliestVoteWins => leading_options
+                    .iter()
+                    .min_by_key(|&&index| proposal.last_vote_ordinals[index])
+                    .copied(),
+                TiePolicy::ProposerPreference => self.votes
+                    .get((proposal.id, proposal.proposer))
+                    .map(|vote| vote.choice.option_index as usize)
+                    .filter(|index| leading_options.contains(index)),
+            }
+        }
+
+        /// Internal helper to open a short runoff between the tied options of a proposal
+        fn create_runoff(&mut self, parent: &Proposal, tied_options: &[usize]) -> Result<u32> {
+            let options = tied_options
+                .iter()
+                .map(|&index| parent.voting_options.options[index].clone())
+                .collect();
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: parent.governance_params.quorum_threshold.clone(),
+                execution_delay: parent.governance_params.execution_delay.clone(),
+                // A runoff that ties again is rejected rather than spawning another runoff
+                tie_policy: TiePolicy::Reject,
+                early_decision: parent.governance_params.early_decision,
+            };
+            
+            let mut runoff = self.build_proposal(
+                format!("Runoff: {}", parent.title),
+                parent.description.clone(),
+                parent.proposal_type.clone(),
+                governance_params,
+                VotingOptions { options },
+                parent.proposer,
+            )?;
+            runoff.parent_proposal = Some(parent.id);
+            self.store_new_proposal(&runoff)
+        }
+
+        /// Internal helper running sequential Phragmén over an election's approval ballots,
+        /// returning the elected candidates in the order their seats were filled
+        fn run_phragmen(&self, proposal: &Proposal) -> Result<Vec<u32>> {
+            let ballots: Vec<ApprovalBallot> = (0..proposal.total_voters)
+                .filter_map(|index| self.proposal_voters.get((proposal.id, index)))
+                .filter_map(|voter| self.election_ballots.get((proposal.id, voter)))
+                .collect();
+            let seats = proposal.seats.unwrap_or(0) as usize;
+            let candidate_count = proposal.voting_options.options.len();
+            
+            let mut loads = vec![0u128; ballots.len()];
+            let mut winners: Vec<u32> = Vec::new();
+            
+            while winners.len() < seats {
+                // Score each unelected candidate by the load its voters would carry
+                let mut best: Option<(u32, u128)> = None;
+                for candidate in 0..candidate_count as u32 {
+                    if winners.contains(&candidate) {
+                        continue;
+                    }
+                    
+                    let mut approval_stake = 0u128;
+                    let mut weighted_load = 0u128;
+                    for (ballot, load) in ballots.iter().zip(&loads) {
+                        if ballot.approvals.contains(&candidate) {
+                            approval_stake = approval_stake.checked_add(ballot.weight)
+                                .ok_or(Error::ArithmeticOverflow)?;
+                            weighted_load = ballot.weight.checked_mul(*load)
+                                .and_then(|load| weighted_load.checked_add(load))
+                                .ok_or(Error::ArithmeticOverflow)?;
+                        }
+                    }
+                    if approval_stake == 0 {
+                        continue;
+                    }
+                    
+                    let score = PHRAGMEN_SCALE.checked_add(weighted_load)
+                        .ok_or(Error::ArithmeticOverflow)? / approval_stake;
+                    // Lowest score wins; ties go to the lower candidate index
+                    if best.is_none_or(|(_, best_score)| score < best_score) {
+                        best = Some((candidate, score));
+                    }
+                }
+                
+                // Stop early when no remaining candidate has any approvals
+                let Some((elected, score)) = best else {
+                    break;
+                };
+                for (ballot, load) in ballots.iter().zip(loads.iter_mut()) {
+                    if ballot.approvals.contains(&elected) {
+                        *load = score;
+                    }
+                }
+                winners.push(elected);
+            }
+            
+            Ok(winners)
+        }
+
+        /// Internal helper to check if quorum is reached
+        fn has_reached_quorum_internal(&self, proposal: &Proposal) -> Result<bool> {
+            // Early-decision proposals are voted on by their snapshot electorate alone
+            let electorate = if proposal.governance_params.early_decision {
+                proposal.electorate
+            } else {
+                self.total_voters
+            };
+            if electorate == 0 {
+                return Ok(false);
+            }
+            
+            let quorum_percentage = proposal.governance_params.quorum_threshold.to_percentage();
+            let required_votes = (electorate as u128 * quorum_percentage as u128) / 100;
+            // Approval ballots count once each, however many candidates they approve
+            let total_votes: u128 = if proposal.seats.is_some() {
+                proposal.total_voters as u128
+            } else {
+                proposal.vote_counts.iter().sum()
+            };
+            
+            Ok(total_votes >= required_votes)
+        }
+
+        // Query functions
+
+        /// Get a specific proposal by ID
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Get all proposal IDs
+        #[ink(message)]
+        pub fn get_all_proposal_ids(&self) -> Vec<u32> {
+            self.proposal_ids.clone()
+        }
+
+        /// Get user's vote on a specific proposal
+        #[ink(message)]
+        pub fn get_user_vote(&self, proposal_id: u32, user: H160) -> Option<Vote> {
+            self.votes.get((proposal_id, user))
+        }
+
+        /// Get contract statistics
+        #[ink(message)]
+        pub fn get_stats(&self) -> ContractStats {
+            let mut active_proposals = 0u32;
+            let mut executed_proposals = 0u32;
+            
+            for proposal_id in &self.proposal_ids {
+                if let Some(proposal) = self.proposals.get(proposal_id) {
+                    match proposal.status {
+                        ProposalStatus::Active | ProposalStatus::Confirming => active_proposals += 1,
+                        ProposalStatus::Executed => executed_proposals += 1,
+                        _ => {}
+                    }
+                }
+            }
+            
+            let pots = self.pot_names
+                .iter()
+                .filter_map(|name| self.pots.get(name))
+                .map(|pot| PotSummary {
+                    name: pot.name,
+                    balance: pot.balance,
+                    liabilities: pot.liabilities,
+                })
+                .collect();
+            
+            ContractStats {
+                total_proposals: self.proposal_ids.len() as u32,
+                active_proposals,
+                executed_proposals,
+                total_voters: self.total_voters,
+                pots,
+            }
+        }
+
+        /// Get total number of registered voters
+        #[ink(message)]
+        pub fn get_total_voters(&self) -> u32 {
+            self.total_voters
+        }
+
+        /// Check if a proposal has reached quorum
+        #[ink(message)]
+        pub fn has_reached_quorum(&self, proposal_id: u32) -> Result<bool> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            self.has_reached_quorum_internal(&proposal)
+        }
+
+        /// Get proposal results with vote counts
+        #[ink(message)]
+        pub fn get_proposal_results(&self, proposal_id: u32) -> Result<(Vec<u128>, bool)> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
+            Ok((proposal.vote_counts, quorum_reached))
+        }
+
+        /// Get voting options for a proposal
+        #[ink(message)]
+        pub fn get_voting_options(&self, proposal_id: u32) -> Result<Vec<String>> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            Ok(proposal.voting_options.options)
+        }
+
+        /// Get detailed results with option names
+        #[ink(message)]
+        pub fn get_detailed_results(&self, proposal_id: u32) -> Result<ProposalResults> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            let quorum_reached = self.has_reached_quorum_internal(&proposal)?;
+            let total_votes: u128 = proposal.vote_counts.iter().sum();
+            
+            // Find winning option, settling ties with the proposal's tie policy
+            let winning_option = self.resolve_winner(&proposal).map(|index| (
+                index as u32,
+                proposal.voting_options.options[index].clone(),
+                proposal.vote_counts[index],
+            ));
+            
+            Ok(ProposalResults {
+                proposal_id,
+                vote_counts: proposal.vote_counts,
+                option_names: proposal.voting_options.options,
+                total_votes,
+                quorum_reached,
+                winning_option,
+            })
+        }
+
+        /// Get the elected candidates of a finalized election, in the order their seats were filled
+        #[ink(message)]
+        pub fn get_election_winners(&self, proposal_id: u32) -> Result<Vec<(u32, String)>> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            if proposal.seats.is_none() {
+                return Err(Error::NotAnElection);
+            }
+            
+            let winners = self.election_winners.get(proposal_id).unwrap_or_default();
+            Ok(winners
+                .into_iter()
+                .map(|index| (index, proposal.voting_options.options[index as usize].clone()))
+                .collect())
+        }
+
+        /// Get a voter's approval ballot on an election
+        #[ink(message)]
+        pub fn get_user_approvals(&self, proposal_id: u32, user: H160) -> Option<ApprovalBallot> {
+            self.election_ballots.get((proposal_id, user))
+        }
+
+        /// Get the native balance held by the treasury
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            Balance::try_from(self.env().balance())
+                .unwrap_or(Balance::MAX)
+                .saturating_sub(self.burned_funds)
+        }
+
+        /// Get the PSP22 tokens the treasury accepts
+        #[ink(message)]
+        pub fn get_accepted_assets(&self) -> Vec<H160> {
+            self.accepted_assets.clone()
+        }
+
+        /// Get the treasury's balance of an asset
+        #[ink(message)]
+        pub fn get_asset_balance(&self, asset: Asset) -> Result<Balance> {
+            match asset {
+                Asset::Native => Ok(self.get_treasury_balance()),
+                Asset::Psp22(token) => self.psp22_balance_of(token),
+            }
+        }
+
+        /// Get the treasury's balance of an asset not promised to approved spends
+        #[ink(message)]
+        pub fn get_available_asset_balance(&self, asset: Asset) -> Result<Balance> {
+            self.available_balance_of(&asset)
+        }
+
+        /// Get the treasury balance not yet promised to approved spends or escrowed grants
+        #[ink(message)]
+        pub fn get_available_balance(&self) -> Balance {
+            self.get_treasury_balance()
+                .saturating_sub(self.approved_liabilities)
+                .saturating_sub(self.escrowed_funds)
+                .saturating_sub(self.pots_total)
+                .saturating_sub(self.held_deposits)
+        }
+
+        /// Get the version of the deployed code
+        #[ink(message)]
+        pub fn contract_version(&self) -> u32 {
+            CONTRACT_VERSION
+        }
+
+        /// Get a bounty by the id of the proposal that funded it
+        #[ink(message)]
+        pub fn get_bounty(&self, bounty_id: u32) -> Option<Bounty> {
+            self.bounties.get(bounty_id)
+        }
+
+        /// Get an open claim by id
+        #[ink(message)]
+        pub fn get_claim(&self, claim_id: u32) -> Option<Claim> {
+            self.claims.get(claim_id)
+        }
+
+        /// Get the open claims of a beneficiary, including expired ones not yet returned
+        #[ink(message)]
+        pub fn get_open_claims(&self, beneficiary: H160) -> Vec<Claim> {
+            self.beneficiary_claims
+                .get(beneficiary)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|claim_id| self.claims.get(claim_id))
+                .collect()
+        }
+
+        /// Get the members who seconded a proposal
+        #[ink(message)]
+        pub fn get_proposal_seconds(&self, proposal_id: u32) -> Vec<H160> {
+            self.proposal_seconds.get(proposal_id).unwrap_or_default()
+        }
+
+        /// Get a tip by id
+        #[ink(message)]
+        pub fn get_tip(&self, tip_id: u32) -> Option<Tip> {
+            self.tips.get(tip_id)
+        }
+
+        /// Get a pot by name
+        #[ink(message)]
+        pub fn get_pot(&self, name: String) -> Option<Pot> {
+            self.pots.get(&name)
+        }
+
+        /// Check whether the outflow circuit breaker has paused treasury spending
+        #[ink(message)]
+        pub fn is_outflow_paused(&self) -> bool {
+            self.outflow_paused
+        }
+
+        /// Get the block at which the next spend period can be processed, if spend periods are enabled
+        #[ink(message)]
+        pub fn next_spend_period(&self) -> Option<u32> {
+            self.spend_period_config.as_ref().map(|_| self.next_spend_period)
+        }
+
+        /// Get the proposals waiting in the approvals queue, in payout order
+        #[ink(message)]
+        pub fn get_approvals_queue(&self) -> Vec<u32> {
+            self.approvals_queue.clone()
+        }
+
+        /// Get the report of a processed spend period
+        #[ink(message)]
+        pub fn get_spend_report(&self, period: u32) -> Option<SpendPeriodReport> {
+            self.spend_reports.get(period)
+        }
+
+        /// Get a stream by the ID of the proposal that created it
+        #[ink(message)]
+        pub fn get_stream(&self, stream_id: u32) -> Option<Stream> {
+            self.streams.get(stream_id)
+        }
+
+        /// Get the amount a stream's beneficiary can claim right now
+        #[ink(message)]
+        pub fn get_claimable_stream_amount(&self, stream_id: u32) -> Result<Balance> {
+            let stream = self.streams.get(stream_id)
+                .ok_or(Error::StreamNotFound)?;
+            Ok(Self::vested_amount(&stream, self.env().block_number())?.saturating_sub(stream.claimed))
+        }
+
+        /// Get an escrowed grant and the state of its milestones
+        #[ink(message)]
+        pub fn get_grant(&self, proposal_id: u32) -> Option<Grant> {
+            self.grants.get(proposal_id)
+        }
+
+        /// Get the current council members
+        #[ink(message)]
+        pub fn get_council_members(&self) -> Vec<H160> {
+            self.council_members.clone()
+        }
+
+        /// Get the council's prime member
+        #[ink(message)]
+        pub fn get_council_prime(&self) -> Option<H160> {
+            self.council_prime
+        }
+
+        /// Get an open council motion on a proposal
+        #[ink(message)]
+        pub fn get_council_motion(&self, proposal_id: u32, kind: CouncilMotionKind) -> Option<CouncilMotion> {
+            self.council_motions.get(&(proposal_id, kind))
+        }
+
+        /// Get the winning option for a proposal
+        #[ink(message)]
+        pub fn get_winning_option(&self, proposal_id: u32) -> Result<Option<(u32, String, u128)>> {
+            let proposal = self.proposals.get(proposal_id)
+                .ok_or(Error::ProposalNotFound)?;
+            
+            // Return None if there's no votes or an unresolved tie
+            Ok(self.resolve_winner(&proposal).map(|index| (
+                index as u32,
+                proposal.voting_options.options[index].clone(),
+                proposal.vote_counts[index],
+            )))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::prelude::vec;
+
+        fn set_caller(caller: H160) {
+            ink::env::test::set_caller(caller);
+        }
+
+        /// Accepts both block numbers and block-clock proposal times
+        fn set_block_number(block: impl TryInto<u32>) {
+            let block = block.try_into().ok().expect("block number out of range");
+            ink::env::test::set_block_number::<ink::env::DefaultEnvironment>(block);
+        }
+
+        /// What a test proposal carries
+        enum Payload {
+            /// A Yes/No proposal with no action
+            Plain,
+            /// A plain proposal whose voting opens at the given clock time
+            Draft(u64),
+            /// A plain proposal that finalizes early once its outcome is settled
+            EarlyDecision,
+            Spend(TreasurySpend),
+            Stream(TreasurySpend, PayoutSchedule),
+            Grant(GrantTerms),
+            Bounty(Balance, BountyTerms),
+            Pot(PotAction),
+            Config(Vec<ConfigChange>),
+        }
+
+        /// Creates a proposal as the caller with three days of voting, a 10% quorum and immediate execution
+        fn create(contract: &mut TreasuryGovernance, payload: Payload) -> Result<u32> {
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            let title = String::from("Test Proposal");
+            let description = String::from("Created by a test");
+            let voting_options = VotingOptions { options: vec![String::from("Yes"), String::from("No")] };
+            
+            match payload {
+                Payload::Plain => contract.create_proposal(
+                    title, description, ProposalType::Other, governance_params, voting_options,
+                ),
+                Payload::Draft(voting_start) => contract.create_draft_proposal(
+                    title, description, ProposalType::Other, governance_params, voting_options, voting_start,
+                ),
+                Payload::EarlyDecision => contract.create_proposal(
+                    title,
+                    description,
+                    ProposalType::Other,
+                    GovernanceParameters { early_decision: true, ..governance_params },
+                    voting_options,
+                ),
+                Payload::Spend(spend) => contract.create_spend_proposal(title, description, governance_params, spend),
+                Payload::Stream(spend, schedule) => contract.create_stream_proposal(
+                    title, description, governance_params, spend, schedule,
+                ),
+                Payload::Grant(terms) => contract.create_grant_proposal(title, description, governance_params, terms),
+                Payload::Bounty(amount, terms) => contract.create_bounty_proposal(
+                    title, description, governance_params, amount, terms,
+                ),
+                Payload::Pot(action) => contract.create_pot_proposal(title, description, governance_params, action),
+                Payload::Config(changes) => contract.create_config_proposal(title, description, governance_params, changes),
+            }
+        }
+
+        /// Has Alice create a proposal and vote it through, then finalizes it once voting ends.
+        /// With `execute` the passed proposal is also executed.
+        fn pass_and_execute(contract: &mut TreasuryGovernance, payload: Payload, execute: bool) -> u32 {
+            let accounts = ink::env::test::default_accounts();
+            
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = create(contract, payload).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            if execute {
+                contract.execute_proposal(proposal_id).unwrap();
+            }
+            proposal_id
+        }
+
+        /// Creates a Yes/No proposal with the given tie policy where Alice and Bob
+        /// split their votes, Alice first, then ends its voting period
+        fn create_tied_proposal(contract: &mut TreasuryGovernance, tie_policy: TiePolicy, alice_choice: u32) -> u32 {
+            let accounts = ink::env::test::default_accounts();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = contract.create_proposal(
+                String::from("Tied Proposal"),
+                String::from("A proposal that ends in a tie"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            contract.vote(proposal_id, alice_choice).unwrap();
+            
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 1 - alice_choice).unwrap();
+            
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            proposal_id
+        }
+
+        /// Creates a Yes/No proposal that Alice votes through, then finalizes it
+        fn create_passed_proposal(contract: &mut TreasuryGovernance, proposal_type: ProposalType, execution_delay: ExecutionDelay) -> u32 {
+            let accounts = ink::env::test::default_accounts();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = contract.create_proposal(
+                String::from("Passed Proposal"),
+                String::from("A proposal that passes"),
+                proposal_type,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+            proposal_id
+        }
+
+        #[ink::test]
+        fn test_constructor() {
+            let contract = TreasuryGovernance::new();
+            assert_eq!(contract.next_proposal_id, 1);
+            assert_eq!(contract.total_voters, 0);
+            assert_eq!(contract.proposal_ids.len(), 0);
+        }
+
+        #[ink::test]
+        fn test_voter_registration() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register first voter
+            assert!(contract.register_voter().is_ok());
+            assert_eq!(contract.get_total_voters(), 1);
+            
+            // Register same voter again (should not increase count)
+            assert!(contract.register_voter().is_ok());
+            assert_eq!(contract.get_total_voters(), 1);
+        }
+
+        #[ink::test]
+        fn test_create_proposal_success() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![
+                    String::from("Yes"),
+                    String::from("No"),
+                ],
+            };
+            
+            let result = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal for governance"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            );
+            
+            assert!(result.is_ok());
+            let proposal_id = result.unwrap();
+            assert_eq!(proposal_id, 1);
+            assert_eq!(contract.next_proposal_id, 2);
+            assert_eq!(contract.proposal_ids.len(), 1);
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.title, "Test Proposal");
+            assert_eq!(proposal.status, ProposalStatus::Active);
+            assert_eq!(proposal.vote_counts.len(), 2);
+        }
+
+        #[ink::test]
+        fn test_voting_success() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register voter first
+            contract.register_voter().unwrap();
+            
+            // Create proposal
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![
+                    String::from("Yes"),
+                    String::from("No"),
+                ],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // Vote on proposal
+            let result = contract.vote(proposal_id, 0);
+            assert!(result.is_ok());
+            
+            // Check that the vote was recorded by verifying proposal updates
+            // Note: Individual vote lookup may fail due to test environment account differences
+            
+            // Check vote counts updated
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.vote_counts[0], 1);
+            assert_eq!(proposal.vote_counts[1], 0);
+            assert_eq!(proposal.total_voters, 1);
+        }
+
+        #[ink::test]
+        fn test_create_proposal_no_voting_options() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![], // Empty options should fail
+            };
+            
+            let result = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            );
+            
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::NoVotingOptions);
+        }
+
+        #[ink::test]
+        fn test_create_proposal_too_many_options() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            // Create 11 options (should fail as max is 10)
+            let mut options = Vec::new();
+            for i in 0..11 {
+                options.push(format!("Option {}", i));
+            }
+            
+            let voting_options = VotingOptions { options };
+            
+            let result = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            );
+            
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::TooManyVotingOptions);
+        }
+
+        #[ink::test]
+        fn test_vote_unregistered_voter() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Create proposal without registering voter
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // Try to vote without being registered
+            let result = contract.vote(proposal_id, 0);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::NotRegisteredVoter);
+        }
+
+        #[ink::test]
+        fn test_vote_nonexistent_proposal() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register voter
+            contract.register_voter().unwrap();
+            
+            // Try to vote on non-existent proposal
+            let result = contract.vote(999, 0);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::ProposalNotFound);
+        }
+
+        #[ink::test]
+        fn test_vote_invalid_option_index() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register voter
+            contract.register_voter().unwrap();
+            
+            // Create proposal with 2 options
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // Try to vote with invalid option index (2 when only 0,1 exist)
+            let result = contract.vote(proposal_id, 2);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::InvalidOptionIndex);
+        }
+
+        #[ink::test]
+        fn test_double_voting_same_voter() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register voter
+            contract.register_voter().unwrap();
+            
+            // Create proposal
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Test Proposal"),
+                String::from("A test proposal"),
+                ProposalType::Treasury,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // First vote should succeed
+            let result = contract.vote(proposal_id, 0);
+            assert!(result.is_ok());
+            
+            // Second vote should fail (already voted)
+            let result = contract.vote(proposal_id, 1);
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err(), Error::AlreadyVoted);
+        }
+
+        #[ink::test]
+        fn test_get_proposal_details() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThirtyDays,
+                quorum_threshold: QuorumThreshold::TwentyFive,
+                execution_delay: ExecutionDelay::SevenDays,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![
+                    String::from("Approve"),
+                    String::from("Reject"),
+                    String::from("Abstain"),
+                ],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Treasury Funding"),
+                String::from("Fund development project"),
+                ProposalType::Treasury,
+                governance_params.clone(),
+                voting_options.clone(),
+            ).unwrap();
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.title, "Treasury Funding");
+            assert_eq!(proposal.description, "Fund development project");
+            assert_eq!(proposal.proposal_type, ProposalType::Treasury);
+            assert_eq!(proposal.governance_params.voting_period, VotingPeriod::ThirtyDays);
+            assert_eq!(proposal.governance_params.quorum_threshold, QuorumThreshold::TwentyFive);
+            assert_eq!(proposal.governance_params.execution_delay, ExecutionDelay::SevenDays);
+            assert_eq!(proposal.voting_options.options.len(), 3);
+            assert_eq!(proposal.status, ProposalStatus::Active);
+        }
+
+        #[ink::test]
+        fn test_get_nonexistent_proposal() {
+            let contract = TreasuryGovernance::new();
+            
+            let result = contract.get_proposal(999);
+            assert!(result.is_none());
+        }
+
+        #[ink::test]
+        fn test_multiple_proposal_types() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            // Create different types of proposals
+            let treasury_id = contract.create_proposal(
+                String::from("Treasury Proposal"),
+                String::from("Treasury funding"),
+                ProposalType::Treasury,
+                governance_params.clone(),
+                voting_options.clone(),
+            ).unwrap();
+            
+            let governance_id = contract.create_proposal(
+                String::from("Governance Proposal"),
+                String::from("Change governance rules"),
+                ProposalType::Governance,
+                governance_params.clone(),
+                voting_options.clone(),
+            ).unwrap();
+            
+            let technical_id = contract.create_proposal(
+                String::from("Technical Proposal"),
+                String::from("Technical upgrade"),
+                ProposalType::Technical,
+                governance_params.clone(),
+                voting_options.clone(),
+            ).unwrap();
+            
+            let other_id = contract.create_proposal(
+                String::from("Other Proposal"),
+                String::from("Other type"),
+                ProposalType::Other,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // Verify all proposals were created with correct types
+            assert_eq!(contract.get_proposal(treasury_id).unwrap().proposal_type, ProposalType::Treasury);
+            assert_eq!(contract.get_proposal(governance_id).unwrap().proposal_type, ProposalType::Governance);
+            assert_eq!(contract.get_proposal(technical_id).unwrap().proposal_type, ProposalType::Technical);
+            assert_eq!(contract.get_proposal(other_id).unwrap().proposal_type, ProposalType::Other);
+            
+            assert_eq!(contract.proposal_ids.len(), 4);
+        }
+
+        #[ink::test]
+        fn test_get_all_proposal_ids() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            // Initially no proposals
+            assert_eq!(contract.get_all_proposal_ids().len(), 0);
+            
+            // Create 3 proposals
+            for i in 1..=3 {
+                contract.create_proposal(
+                    format!("Proposal {}", i),
+                    format!("Description {}", i),
+                    ProposalType::Treasury,
+                    governance_params.clone(),
+                    voting_options.clone(),
+                ).unwrap();
+            }
+            
+            let proposal_ids = contract.get_all_proposal_ids();
+            assert_eq!(proposal_ids.len(), 3);
+            assert_eq!(proposal_ids, vec![1, 2, 3]);
+        }
+
+        #[ink::test]
+        fn test_contract_stats() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Initially no proposals or voters
+            let stats = contract.get_stats();
+            assert_eq!(stats.total_proposals, 0);
+            assert_eq!(stats.total_voters, 0);
+            assert_eq!(stats.active_proposals, 0);
+            assert_eq!(stats.executed_proposals, 0);
+            
+            // Register voters
+            contract.register_voter().unwrap();
+            
+            // Create proposals
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            contract.create_proposal(
+                String::from("Proposal 1"),
+                String::from("Description 1"),
+                ProposalType::Treasury,
+                governance_params.clone(),
+                voting_options.clone(),
+            ).unwrap();
+            
+            contract.create_proposal(
+                String::from("Proposal 2"),
+                String::from("Description 2"),
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            let stats = contract.get_stats();
+            assert_eq!(stats.total_proposals, 2);
+            assert_eq!(stats.total_voters, 1);
+            assert_eq!(stats.active_proposals, 2);
+            assert_eq!(stats.executed_proposals, 0);
+        }
+
+        #[ink::test]
+        fn test_multi_option_voting() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Register voter
+            contract.register_voter().unwrap();
+            
+            // Create proposal with multiple options
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![
+                    String::from("Option A"),
+                    String::from("Option B"),
+                    String::from("Option C"),
+                    String::from("Option D"),
+                ],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Multi-Option Proposal"),
+                String::from("Choose from multiple options"),
+                ProposalType::Governance,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            
+            // Vote for option C (index 2)
+            let result = contract.vote(proposal_id, 2);
+            assert!(result.is_ok());
+            
+            // Check vote was recorded correctly
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.vote_counts[0], 0); // Option A
+            assert_eq!(proposal.vote_counts[1], 0); // Option B
+            assert_eq!(proposal.vote_counts[2], 1); // Option C
+            assert_eq!(proposal.vote_counts[3], 0); // Option D
+            assert_eq!(proposal.total_voters, 1);
+        }
+
+        #[ink::test]
+        fn test_different_governance_parameters() {
+            let mut contract = TreasuryGovernance::new();
+            
+            // Test different voting periods
+            let short_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Five,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let long_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThirtyDays,
+                quorum_threshold: QuorumThreshold::TwentyFive,
+                execution_delay: ExecutionDelay::SevenDays,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            let short_proposal = contract.create_proposal(
+                String::from("Short Proposal"),
+                String::from("Quick decision"),
+                ProposalType::Technical,
+                short_params,
+                voting_options.clone(),
+            ).unwrap();
+            
+            let long_proposal = contract.create_proposal(
+                String::from("Long Proposal"),
+                String::from("Important decision"),
+                ProposalType::Treasury,
+                long_params,
+                voting_options,
+            ).unwrap();
+            
+            // Verify parameters were set correctly
+            let short_prop = contract.get_proposal(short_proposal).unwrap();
+            assert_eq!(short_prop.governance_params.voting_period, VotingPeriod::ThreeDays);
+            assert_eq!(short_prop.governance_params.quorum_threshold, QuorumThreshold::Five);
+            assert_eq!(short_prop.governance_params.execution_delay, ExecutionDelay::Immediately);
+            
+            let long_prop = contract.get_proposal(long_proposal).unwrap();
+            assert_eq!(long_prop.governance_params.voting_period, VotingPeriod::ThirtyDays);
+            assert_eq!(long_prop.governance_params.quorum_threshold, QuorumThreshold::TwentyFive);
+            assert_eq!(long_prop.governance_params.execution_delay, ExecutionDelay::SevenDays);
+        }
+
+        #[ink::test]
+        fn test_tie_rejected_by_default() {
+            let mut contract = TreasuryGovernance::new();
+            let proposal_id = create_tied_proposal(&mut contract, TiePolicy::Reject, 0);
+            
+            contract.update_proposal_status(proposal_id).unwrap();
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Rejected);
+            assert_eq!(contract.get_winning_option(proposal_id).unwrap(), None);
+        }
+
+        #[ink::test]
+        fn test_tie_earliest_vote_wins() {
+            let mut contract = TreasuryGovernance::new();
+            // Alice votes "No" first, so "No" reaches the tied count before "Yes"
+            let proposal_id = create_tied_proposal(&mut contract, TiePolicy::EarliestVoteWins, 1);
+            
+            contract.update_proposal_status(proposal_id).unwrap();
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Passed);
+            assert_eq!(
+                contract.get_winning_option(proposal_id).unwrap(),
+                Some((1, String::from("No"), 1))
+            );
+            assert_eq!(
+                contract.get_detailed_results(proposal_id).unwrap().winning_option,
+                Some((1, String::from("No"), 1))
+            );
+        }
+
+        #[ink::test]
+        fn test_tie_proposer_preference() {
+            let mut contract = TreasuryGovernance::new();
+            // Alice is the proposer and votes "Yes"
+            let proposal_id = create_tied_proposal(&mut contract, TiePolicy::ProposerPreference, 0);
+            
+            contract.update_proposal_status(proposal_id).unwrap();
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Passed);
+            assert_eq!(
+                contract.get_winning_option(proposal_id).unwrap(),
+                Some((0, String::from("Yes"), 1))
+            );
+        }
+
+        #[ink::test]
+        fn test_tie_creates_runoff() {
+            let mut contract = TreasuryGovernance::new();
+            let proposal_id = create_tied_proposal(&mut contract, TiePolicy::Runoff, 0);
+            
+            contract.update_proposal_status(proposal_id).unwrap();
+            
+            let parent = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(parent.status, ProposalStatus::RunoffScheduled);
+            let runoff_id = parent.runoff_proposal.unwrap();
+            
+            let runoff = contract.get_proposal(runoff_id).unwrap();
+            assert_eq!(runoff.status, ProposalStatus::Active);
+            assert_eq!(runoff.parent_proposal, Some(proposal_id));
+            assert_eq!(runoff.proposer, parent.proposer);
+            assert_eq!(runoff.voting_options.options, vec![String::from("Yes"), String::from("No")]);
+            assert_eq!(runoff.governance_params.voting_period, VotingPeriod::ThreeDays);
+            assert_eq!(runoff.voting_end, u64::from(runoff.created_at + VotingPeriod::ThreeDays.to_blocks()));
+            assert_eq!(contract.get_all_proposal_ids(), vec![proposal_id, runoff_id]);
+        }
+
+        #[ink::test]
+        fn test_runoff_rejected_for_proposals_carrying_actions() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let result = contract.create_spend_proposal(
+                String::from("Tied Spend"),
+                String::from("A spend a runoff could not pay"),
+                GovernanceParameters {
+                    voting_period: VotingPeriod::ThreeDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::Immediately,
+                    tie_policy: TiePolicy::Runoff,
+                    early_decision: false,
+                },
+                TreasurySpend {
+                    recipient: SpendRecipient::Single(accounts.django),
+                    amount: 100,
+                    asset: Asset::Native,
+                    pot: None,
+                },
+            );
+            assert_eq!(result, Err(Error::RunoffNotSupported));
+        }
+
+        #[ink::test]
+        fn test_election_phragmen_allocates_seats_proportionally() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let candidates = VotingOptions {
+                options: vec![
+                    String::from("Alice"),
+                    String::from("Bob"),
+                    String::from("Charlie"),
+                ],
+            };
+            
+            let proposal_id = contract.create_election(
+                String::from("Council Election"),
+                String::from("Elect two council members"),
+                governance_params,
+                candidates,
+                2,
+            ).unwrap();
+            
+            // Three voters approve Alice and Bob, two approve only Charlie
+            for voter in [accounts.alice, accounts.bob, accounts.charlie] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+                contract.vote_approval(proposal_id, vec![0, 1]).unwrap();
+            }
+            for voter in [accounts.django, accounts.eve] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+                contract.vote_approval(proposal_id, vec![2]).unwrap();
+            }
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.vote_counts, vec![3, 3, 2]);
+            assert_eq!(proposal.total_voters, 5);
+            assert_eq!(contract.get_user_approvals(proposal_id, accounts.eve).unwrap().approvals, vec![2]);
+            
+            let voting_end = proposal.voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            
+            // The minority bloc still wins a seat despite Bob's higher approval count
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+            assert_eq!(
+                contract.get_election_winners(proposal_id).unwrap(),
+                vec![(0, String::from("Alice")), (2, String::from("Charlie"))]
+            );
+        }
+
+        #[ink::test]
+        fn test_create_election_invalid_seats() {
+            let mut contract = TreasuryGovernance::new();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let candidates = VotingOptions {
+                options: vec![String::from("Alice"), String::from("Bob")],
+            };
+            
+            for seats in [0, 3] {
+                let result = contract.create_election(
+                    String::from("Council Election"),
+                    String::from("Elect council members"),
+                    governance_params.clone(),
+                    candidates.clone(),
+                    seats,
+                );
+                assert_eq!(result.unwrap_err(), Error::InvalidSeatCount);
+            }
+            
+            // Elections cannot be created without a seat count
+            let result = contract.create_proposal(
+                String::from("Council Election"),
+                String::from("Elect council members"),
+                ProposalType::Election,
+                governance_params,
+                candidates,
+            );
+            assert_eq!(result.unwrap_err(), Error::InvalidProposal);
+        }
+
+        #[ink::test]
+        fn test_vote_approval_invalid_ballots() {
+            let mut contract = TreasuryGovernance::new();
+            contract.register_voter().unwrap();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let candidates = VotingOptions {
+                options: vec![String::from("Alice"), String::from("Bob")],
+            };
+            
+            let proposal_id = contract.create_election(
+                String::from("Council Election"),
+                String::from("Elect one council member"),
+                governance_params,
+                candidates,
+                1,
+            ).unwrap();
+            
+            assert_eq!(contract.vote_approval(proposal_id, vec![]).unwrap_err(), Error::EmptyBallot);
+            assert_eq!(contract.vote_approval(proposal_id, vec![0, 0]).unwrap_err(), Error::InvalidOptionIndex);
+            assert_eq!(contract.vote_approval(proposal_id, vec![2]).unwrap_err(), Error::InvalidOptionIndex);
+            assert_eq!(contract.vote(proposal_id, 0).unwrap_err(), Error::ApprovalBallotRequired);
+            
+            assert!(contract.vote_approval(proposal_id, vec![1]).is_ok());
+            assert_eq!(contract.vote_approval(proposal_id, vec![0]).unwrap_err(), Error::AlreadyVoted);
+        }
+
+        #[ink::test]
+        fn test_election_ballots_are_capped() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.register_voter().unwrap();
+            
+            let proposal_id = contract.create_election(
+                String::from("Council Election"),
+                String::from("Elect one council member"),
+                GovernanceParameters {
+                    voting_period: VotingPeriod::SevenDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::OneDay,
+                    tie_policy: TiePolicy::Reject,
+                    early_decision: false,
+                },
+                VotingOptions { options: vec![String::from("Alice"), String::from("Bob")] },
+                1,
+            ).unwrap();
+            
+            for index in 0..MAX_ELECTION_BALLOTS {
+                set_caller(H160::from_low_u64_be(u64::from(index) + 1));
+                contract.register_voter().unwrap();
+                contract.vote_approval(proposal_id, vec![index % 2]).unwrap();
+            }
+            assert_eq!(
+                contract.get_user_approvals(proposal_id, H160::from_low_u64_be(2)).unwrap().approvals,
+                vec![1]
+            );
+            
+            set_caller(accounts.eve);
+            contract.register_voter().unwrap();
+            assert_eq!(contract.vote_approval(proposal_id, vec![0]), Err(Error::ElectionFull));
+        }
+
+        #[ink::test]
+        fn test_council_appointed_by_governance_proposal() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.register_voter().unwrap();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let proposal_id = contract.create_council_proposal(
+                String::from("Appoint Council"),
+                String::from("Seat the first council"),
+                governance_params,
+                CouncilChange::SetMembers(vec![accounts.bob, accounts.charlie]),
+            ).unwrap();
+            assert_eq!(
+                contract.get_proposal(proposal_id).unwrap().council_change,
+                Some(CouncilChange::SetMembers(vec![accounts.bob, accounts.charlie]))
+            );
+            contract.vote(proposal_id, 0).unwrap();
+            
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            contract.execute_proposal(proposal_id).unwrap();
+            
+            assert_eq!(contract.get_council_members(), vec![accounts.bob, accounts.charlie]);
+        }
+
+        #[ink::test]
+        fn test_council_veto_treasury_proposal() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.council_members = vec![accounts.alice, accounts.bob, accounts.charlie];
+            
+            let proposal_id = create_passed_proposal(&mut contract, ProposalType::Treasury, ExecutionDelay::OneDay);
+            
+            set_caller(accounts.alice);
+            contract.council_vote(proposal_id, CouncilMotionKind::Veto, true).unwrap();
+            assert_eq!(
+                contract.close_council_motion(proposal_id, CouncilMotionKind::Veto).unwrap_err(),
+                Error::CouncilMotionNotReady
+            );
+            
+            set_caller(accounts.bob);
+            contract.council_vote(proposal_id, CouncilMotionKind::Veto, true).unwrap();
+            assert!(contract.close_council_motion(proposal_id, CouncilMotionKind::Veto).unwrap());
+            
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Vetoed);
+            let execution_time = contract.get_proposal(proposal_id).unwrap().execution_time;
+            set_block_number(execution_time);
+            assert_eq!(contract.execute_proposal(proposal_id).unwrap_err(), Error::ProposalNotReadyForExecution);
+        }
+
+        #[ink::test]
+        fn test_council_veto_restrictions() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.council_members = vec![accounts.alice];
+            
+            // Only Treasury proposals can be vetoed
+            let governance_id = create_passed_proposal(&mut contract, ProposalType::Governance, ExecutionDelay::OneDay);
+            assert_eq!(
+                contract.council_vote(governance_id, CouncilMotionKind::Veto, true).unwrap_err(),
+                Error::NotVetoable
+            );
+            
+            // Vetoes are only possible before the execution time
+            let treasury_id = create_passed_proposal(&mut contract, ProposalType::Treasury, ExecutionDelay::OneDay);
+            set_block_number(contract.get_proposal(treasury_id).unwrap().execution_time);
+            assert_eq!(
+                contract.council_vote(treasury_id, CouncilMotionKind::Veto, true).unwrap_err(),
+                Error::NotVetoable
+            );
+            
+            // Only council members can vote
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.council_vote(treasury_id, CouncilMotionKind::Veto, true).unwrap_err(),
+                Error::NotCouncilMember
+            );
+        }
+
+        #[ink::test]
+        fn test_council_prime_default_vote() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.council_members = vec![accounts.alice, accounts.bob, accounts.charlie];
+            contract.council_prime = Some(accounts.alice);
+            
+            let proposal_id = create_passed_proposal(&mut contract, ProposalType::Treasury, ExecutionDelay::SevenDays);
+            let opened_at = contract.get_proposal(proposal_id).unwrap().voting_end + 1;
+            
+            // Only the prime votes; Bob and Charlie abstain
+            set_caller(accounts.alice);
+            contract.council_vote(proposal_id, CouncilMotionKind::Veto, true).unwrap();
+            assert_eq!(
+                contract.close_council_motion(proposal_id, CouncilMotionKind::Veto).unwrap_err(),
+                Error::CouncilMotionNotReady
+            );
+            
+            // Once the motion duration ends, abstentions follow the prime's vote
+            set_block_number(opened_at + u64::from(COUNCIL_MOTION_DURATION));
+            assert!(contract.close_council_motion(proposal_id, CouncilMotionKind::Veto).unwrap());
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Vetoed);
+            assert_eq!(contract.get_council_motion(proposal_id, CouncilMotionKind::Veto), None);
+        }
+
+        #[ink::test]
+        fn test_council_fast_track() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.council_members = vec![accounts.alice, accounts.bob, accounts.charlie];
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThirtyDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::SevenDays,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let voting_options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No")],
+            };
+            
+            let proposal_id = contract.create_proposal(
+                String::from("Urgent Fix"),
+                String::from("Patch a critical issue"),
+                ProposalType::Technical,
+                governance_params,
+                voting_options,
+            ).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            
+            // Two of three members meet the two-thirds supermajority
+            set_block_number(10);
+            contract.council_vote(proposal_id, CouncilMotionKind::FastTrack, true).unwrap();
+            set_caller(accounts.bob);
+            contract.council_vote(proposal_id, CouncilMotionKind::FastTrack, true).unwrap();
+            assert!(contract.close_council_motion(proposal_id, CouncilMotionKind::FastTrack).unwrap());
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.voting_end, 10);
+            assert_eq!(proposal.execution_time, 10);
+            
+            set_block_number(11);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert!(contract.execute_proposal(proposal_id).is_ok());
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Executed);
+        }
+
+        #[ink::test]
+        fn test_competing_spends_rechecked_when_passing() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let spend = TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 600,
+                asset: Asset::Native,
+                pot: None,
+            };
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            // Both fit the treasury on their own when proposed
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let mut proposal_ids = Vec::new();
+            for _ in 0..2 {
+                let proposal_id = contract.create_spend_proposal(
+                    String::from("Grant"),
+                    String::from("Fund a contributor"),
+                    governance_params.clone(),
+                    spend.clone(),
+                ).unwrap();
+                contract.vote(proposal_id, 0).unwrap();
+                proposal_ids.push(proposal_id);
+            }
+            
+            let voting_end = contract.get_proposal(proposal_ids[0]).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_ids[0]).unwrap();
+            contract.update_proposal_status(proposal_ids[1]).unwrap();
+            assert_eq!(contract.get_proposal(proposal_ids[0]).unwrap().status, ProposalStatus::Passed);
+            assert_eq!(contract.get_proposal(proposal_ids[1]).unwrap().status, ProposalStatus::Rejected);
+            assert_eq!(contract.approved_liabilities, 600);
+        }
+
+        #[ink::test]
+        fn test_spend_proposal_pays_beneficiary() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let spend = TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 400,
+                asset: Asset::Native,
+                pot: None,
+            };
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(spend.clone()), false);
+            
+            // Voters see exactly what they approve
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().spend, Some(spend));
+            assert_eq!(contract.approved_liabilities, 400);
+            assert_eq!(contract.get_available_balance(), 600);
+            
+            // Execution leaves the funds for the beneficiary to withdraw
+            contract.execute_proposal(proposal_id).unwrap();
+            let claims = contract.get_open_claims(accounts.django);
+            assert_eq!(claims.len(), 1);
+            assert_eq!((claims[0].source, claims[0].amount), (PayoutSource::Proposal(proposal_id), 400));
+            assert_eq!(contract.get_treasury_balance(), 1000);
+            assert_eq!(contract.get_available_balance(), 600);
+            assert_eq!(contract.approved_liabilities, 0);
+            
+            assert_eq!(contract.claim(claims[0].id), Err(Error::NotAuthorized));
+            let django_before = ink::env::test::get_contract_balance::<ink::env::DefaultEnvironment>(accounts.django)
+                .unwrap_or_default();
+            set_caller(accounts.django);
+            assert_eq!(contract.claim(claims[0].id).unwrap(), 400);
+            let django_after = ink::env::test::get_contract_balance::<ink::env::DefaultEnvironment>(accounts.django)
+                .unwrap();
+            
+            assert_eq!(django_after - django_before, U256::from(400));
+            assert_eq!(contract.get_treasury_balance(), 600);
+            assert_eq!(contract.escrowed_funds, 0);
+            assert!(contract.get_open_claims(accounts.django).is_empty());
+        }
+
+        #[ink::test]
+        fn test_rejected_spend_never_executes() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = create(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 400,
+                asset: Asset::Native,
+                pot: None,
+            })).unwrap();
+            contract.vote(proposal_id, 1).unwrap();
+            
+            // Nay winning a spend rejects it rather than passing it with nothing to do
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Rejected);
+            assert_eq!(contract.approved_liabilities, 0);
+            
+            assert_eq!(contract.execute_proposal(proposal_id), Err(Error::ProposalNotReadyForExecution));
+            contract.council_members = vec![accounts.alice];
+            assert_eq!(
+                contract.council_vote(proposal_id, CouncilMotionKind::Veto, true),
+                Err(Error::NotVetoable)
+            );
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Rejected);
+        }
+
+        #[ink::test]
+        fn test_spend_proposal_validated_against_liabilities() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            let zero_spend = TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 0,
+                asset: Asset::Native,
+                pot: None,
+            };
+            let result = contract.create_spend_proposal(
+                String::from("Empty Grant"),
+                String::from("Pays nothing"),
+                governance_params.clone(),
+                zero_spend,
+            );
+            assert_eq!(result.unwrap_err(), Error::InvalidSpend);
+            
+            pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 700,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            
+            // Only 300 remains once the first spend is approved
+            let result = contract.create_spend_proposal(
+                String::from("Second Grant"),
+                String::from("Exceeds the available balance"),
+                governance_params,
+                TreasurySpend {
+                    recipient: SpendRecipient::Single(accounts.eve),
+                    amount: 400,
+                    asset: Asset::Native,
+                    pot: None,
+                },
+            );
+            assert_eq!(result.unwrap_err(), Error::InsufficientTreasuryBalance);
+        }
+
+        #[ink::test]
+        fn test_grant_milestone_released_after_review() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            let proposal_id = pass_and_execute(&mut contract, Payload::Grant(GrantTerms {
+                beneficiary: accounts.django,
+                milestones: vec![
+                    Milestone { amount: 300, deliverable_hash: Hash::from([1u8; 32]) },
+                    Milestone { amount: 200, deliverable_hash: Hash::from([2u8; 32]) },
+                ],
+                reviewers: vec![accounts.bob, accounts.charlie],
+                approvals_required: 2,
+                completion_blocks: 1000,
+            }), true);
+            
+            // Executing the proposal escrows the total instead of paying it
+            assert_eq!(contract.escrowed_funds, 500);
+            assert_eq!(contract.get_treasury_balance(), 1000);
+            assert_eq!(contract.get_available_balance(), 500);
+            
+            // Only the grantee submits evidence, and only reviewers review it
+            set_caller(accounts.eve);
+            assert_eq!(
+                contract.submit_milestone(proposal_id, 0, Hash::from([9u8; 32])).unwrap_err(),
+                Error::NotAuthorized
+            );
+            set_caller(accounts.django);
+            contract.submit_milestone(proposal_id, 0, Hash::from([9u8; 32])).unwrap();
+            assert_eq!(
+                contract.review_milestone(proposal_id, 0, true).unwrap_err(),
+                Error::NotAuthorized
+            );
+            
+            set_caller(accounts.bob);
+            assert_eq!(contract.review_milestone(proposal_id, 0, true).unwrap(), MilestoneStatus::Submitted);
+            set_caller(accounts.charlie);
+            assert_eq!(contract.review_milestone(proposal_id, 0, true).unwrap(), MilestoneStatus::Released);
+            
+            let grant = contract.get_grant(proposal_id).unwrap();
+            assert_eq!(grant.milestones[0].evidence, Some(Hash::from([9u8; 32])));
+            assert_eq!(grant.milestones[1].status, MilestoneStatus::Pending);
+            
+            // The released milestone waits in a claim until the beneficiary withdraws it
+            let claim = contract.get_open_claims(accounts.django)[0].clone();
+            assert_eq!((claim.source, claim.amount), (PayoutSource::Proposal(proposal_id), 300));
+            assert_eq!(contract.escrowed_funds, 500);
+            set_caller(accounts.django);
+            assert_eq!(contract.claim(claim.id), Ok(300));
+            assert_eq!(contract.escrowed_funds, 200);
+            assert_eq!(contract.get_treasury_balance(), 700);
+        }
+
+        #[ink::test]
+        fn test_grant_failed_and_expired_milestones_return_to_treasury() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            let proposal_id = pass_and_execute(&mut contract, Payload::Grant(GrantTerms {
+                beneficiary: accounts.django,
+                milestones: vec![
+                    Milestone { amount: 300, deliverable_hash: Hash::from([1u8; 32]) },
+                    Milestone { amount: 200, deliverable_hash: Hash::from([2u8; 32]) },
+                ],
+                reviewers: vec![accounts.bob, accounts.charlie],
+                approvals_required: 2,
+                completion_blocks: 1000,
+            }), true);
+            
+            // One rejection makes two approvals out of two reviewers unreachable
+            set_caller(accounts.django);
+            contract.submit_milestone(proposal_id, 0, Hash::from([9u8; 32])).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(contract.review_milestone(proposal_id, 0, false).unwrap(), MilestoneStatus::Failed);
+            assert_eq!(contract.escrowed_funds, 200);
+            
+            // The undelivered milestone returns once the deadline passes
+            assert_eq!(contract.close_grant(proposal_id).unwrap_err(), Error::GrantStillOpen);
+            let deadline = contract.get_grant(proposal_id).unwrap().deadline;
+            set_block_number(deadline + 1);
+            assert_eq!(contract.close_grant(proposal_id).unwrap(), 200);
+            
+            assert_eq!(contract.get_grant(proposal_id).unwrap().milestones[1].status, MilestoneStatus::Failed);
+            assert_eq!(contract.escrowed_funds, 0);
+            assert_eq!(contract.get_available_balance(), 1000);
+        }
+
+        #[ink::test]
+        fn test_linear_stream_claims() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            let stream_id = pass_and_execute(&mut contract, Payload::Stream(
+                TreasurySpend {
+                    recipient: SpendRecipient::Single(accounts.django),
+                    amount: 600,
+                    asset: Asset::Native,
+                    pot: None,
+                },
+                PayoutSchedule::Linear { duration: 100 },
+            ), true);
+            
+            let stream = contract.get_stream(stream_id).unwrap();
+            assert_eq!(stream.end_block, stream.start_block + 100);
+            assert_eq!(contract.escrowed_funds, 600);
+            assert_eq!(contract.get_available_balance(), 400);
+            
+            set_caller(accounts.django);
+            set_block_number(stream.start_block + 50);
+            assert_eq!(contract.get_claimable_stream_amount(stream_id).unwrap(), 300);
+            assert_eq!(contract.claim_stream(stream_id).unwrap(), 300);
+            assert_eq!(contract.claim_stream(stream_id).unwrap_err(), Error::NothingToClaim);
+            
+            set_block_number(stream.end_block + 10);
+            assert_eq!(contract.claim_stream(stream_id).unwrap(), 300);
+            
+            assert_eq!(contract.get_stream(stream_id).unwrap().claimed, 600);
+            let claims = contract.get_open_claims(accounts.django);
+            assert_eq!(claims.len(), 2);
+            for claim in claims {
+                contract.claim(claim.id).unwrap();
+            }
+            assert_eq!(contract.escrowed_funds, 0);
+            assert_eq!(contract.get_treasury_balance(), 400);
+            
+            // Only the beneficiary can claim
+            set_caller(accounts.eve);
+            assert_eq!(contract.claim_stream(stream_id).unwrap_err(), Error::NotAuthorized);
+        }
+
+        #[ink::test]
+        fn test_cliff_stream_cancelled_by_governance() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            let stream_id = pass_and_execute(&mut contract, Payload::Stream(
+                TreasurySpend {
+                    recipient: SpendRecipient::Single(accounts.django),
+                    amount: 1000,
+                    asset: Asset::Native,
+                    pot: None,
+                },
+                PayoutSchedule::Cliff { cliff: 10_000, duration: 100_000 },
+            ), true);
+            let start_block = contract.get_stream(stream_id).unwrap().start_block;
+            
+            // Nothing is claimable before the cliff
+            set_caller(accounts.django);
+            set_block_number(start_block + 9_999);
+            assert_eq!(contract.claim_stream(stream_id).unwrap_err(), Error::NothingToClaim);
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            
+            set_caller(accounts.alice);
+            set_block_number(start_block + 10_000);
+            let proposal_id = contract.create_stream_cancellation(
+                String::from("Cancel Salary"),
+                String::from("The contributor has left"),
+                governance_params,
+                stream_id,
+            ).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            contract.execute_proposal(proposal_id).unwrap();
+            
+            // 53,201 of 100,000 blocks had vested when the stream was cancelled
+            let stream = contract.get_stream(stream_id).unwrap();
+            assert_eq!(stream.cancelled_at.map(u64::from), Some(voting_end + 1));
+            assert_eq!(contract.escrowed_funds, 532);
+            assert_eq!(contract.get_available_balance(), 468);
+            
+            // The vested part stays claimable after cancellation
+            set_caller(accounts.django);
+            set_block_number(start_block + 200_000);
+            assert_eq!(contract.claim_stream(stream_id).unwrap(), 532);
+            assert_eq!(contract.get_open_claims(accounts.django)[0].amount, 532);
+            assert_eq!(contract.escrowed_funds, 532);
+        }
+
+        #[ink::test]
+        fn test_spend_period_pays_queue_and_burns() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetSpendPeriodConfig(Some(SpendPeriodConfig {
+                period_blocks: 100_000,
+                burn_percent: 10,
+            }))]), true);
+            let configured_at = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            assert_eq!(contract.next_spend_period(), Some(configured_at + 100_000));
+            
+            let first = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 400,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            let second = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.eve),
+                amount: 500,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            
+            // Executed spends wait in the queue and stay reserved
+            contract.execute_proposal(first).unwrap();
+            contract.execute_proposal(second).unwrap();
+            assert_eq!(contract.get_proposal(first).unwrap().status, ProposalStatus::Queued);
+            assert_eq!(contract.get_approvals_queue(), vec![first, second]);
+            assert_eq!(contract.get_treasury_balance(), 1000);
+            assert_eq!(contract.get_available_balance(), 100);
+            assert_eq!(contract.process_spend_period().unwrap_err(), Error::SpendPeriodNotReached);
+            
+            set_block_number(configured_at + 100_000);
+            let report = contract.process_spend_period().unwrap();
+            assert_eq!(report.paid_proposals, vec![first, second]);
+            assert_eq!(report.total_paid, 900);
+            assert_eq!(report.burned, 10);
+            assert_eq!(contract.get_spend_report(0), Some(report));
+            
+            assert_eq!(contract.get_proposal(second).unwrap().status, ProposalStatus::Executed);
+            assert!(contract.get_approvals_queue().is_empty());
+            assert_eq!(contract.get_open_claims(accounts.eve)[0].amount, 500);
+            assert_eq!(contract.get_available_balance(), 90);
+            
+            // The burn is accounting only; nothing leaves the contract
+            assert_eq!(contract.burned_funds, 10);
+            assert_eq!(contract.get_treasury_balance(), 990);
+            assert_eq!(contract.next_spend_period(), Some(configured_at + 200_000));
+        }
+
+        #[ink::test]
+        fn test_spend_period_defers_unaffordable_approvals() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetSpendPeriodConfig(Some(SpendPeriodConfig {
+                period_blocks: 100_000,
+                burn_percent: 50,
+            }))]), true);
+            let configured_at = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 700,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            contract.execute_proposal(proposal_id).unwrap();
+            
+            // Funds drop below the approved amount before the period closes
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(500));
+            set_block_number(configured_at + 250_000);
+            let report = contract.process_spend_period().unwrap();
+            
+            assert!(report.paid_proposals.is_empty());
+            assert_eq!(report.deferred_proposals, vec![proposal_id]);
+            assert_eq!(report.burned, 0);
+            assert_eq!(contract.get_approvals_queue(), vec![proposal_id]);
+            assert_eq!(contract.next_spend_period(), Some(configured_at + 300_000));
+            
+            // Spend periods cannot be disabled while approvals are queued
+            assert_eq!(
+                contract.apply_config_change(ConfigChange::SetSpendPeriodConfig(None)),
+                Err(Error::InvalidSpendPeriodConfig)
+            );
+        }
+
+        #[ink::test]
+        fn test_single_spend_cap_trips_circuit_breaker() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let limits = OutflowLimits {
+                max_single_spend: Some(300),
+                window_blocks: 1000,
+                max_window_outflow: None,
+                type_budgets: vec![],
+                token_budgets: vec![],
+            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetOutflowLimits(Some(limits))]), true);
+            
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 400,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            
+            // The breach fails without paying or executing the proposal; reporting it pauses outflows
+            assert_eq!(contract.execute_proposal(proposal_id), Err(Error::OutflowLimitExceeded));
+            assert!(!contract.is_outflow_paused());
+            contract.report_outflow_breach(proposal_id).unwrap();
+            assert!(contract.is_outflow_paused());
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+            assert_eq!(contract.get_treasury_balance(), 1000);
+            assert_eq!(contract.execute_proposal(proposal_id).unwrap_err(), Error::TreasuryPaused);
+            
+            // Only governance can lift the pause
+            pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetOutflowLimits(None),
+                ConfigChange::ResumeOutflows,
+            ]), true);
+            contract.execute_proposal(proposal_id).unwrap();
+            assert_eq!(contract.get_available_balance(), 600);
+        }
+
+        #[ink::test]
+        fn test_rolling_window_and_type_budget_caps() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetOutflowLimits(Some(OutflowLimits {
+                max_single_spend: None,
+                window_blocks: 100_000,
+                max_window_outflow: Some(500),
+                type_budgets: vec![(ProposalType::Treasury, 450)],
+                token_budgets: vec![],
+            }))]), true);
+            
+            let first = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 300,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            contract.execute_proposal(first).unwrap();
+            let first_executed_at = contract.get_proposal(first).unwrap().voting_end + 1;
+            
+            // 300 + 200 fits the window cap but not the Treasury budget
+            let second = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.eve),
+                amount: 200,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            assert_eq!(contract.execute_proposal(second), Err(Error::OutflowLimitExceeded));
+            contract.report_outflow_breach(second).unwrap();
+            assert!(contract.is_outflow_paused());
+            assert_eq!(contract.get_proposal(second).unwrap().status, ProposalStatus::Passed);
+            
+            // Once the first spend leaves the window the second one fits
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::ResumeOutflows]), true);
+            set_block_number(first_executed_at + 100_000);
+            contract.execute_proposal(second).unwrap();
+            assert!(!contract.is_outflow_paused());
+            assert_eq!(contract.get_proposal(second).unwrap().status, ProposalStatus::Executed);
+            assert_eq!(contract.get_available_balance(), 500);
+        }
+
+        #[ink::test]
+        fn test_queued_spends_count_against_outflow_window_when_paid() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetSpendPeriodConfig(Some(SpendPeriodConfig {
+                    period_blocks: 100_000,
+                    burn_percent: 0,
+                    })),
+                ConfigChange::SetOutflowLimits(Some(OutflowLimits {
+                    max_single_spend: None,
+                    window_blocks: 200_000,
+                    max_window_outflow: Some(500),
+                    type_budgets: vec![],
+                    token_budgets: vec![],
+                })),
+            ]), true);
+            let configured_at = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            
+            let mut queued = Vec::new();
+            for beneficiary in [accounts.django, accounts.eve] {
+                let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                    recipient: SpendRecipient::Single(beneficiary),
+                    amount: 300,
+                    asset: Asset::Native,
+                    pot: None,
+                }), true);
+                queued.push(proposal_id);
+            }
+            
+            // Queuing records nothing; each spend counts once, when it is paid
+            assert!(contract.recent_outflows.is_empty());
+            set_block_number(configured_at + 100_000);
+            let report = contract.process_spend_period().unwrap();
+            assert_eq!(report.paid_proposals, vec![queued[0]]);
+            assert_eq!(report.deferred_proposals, vec![queued[1]]);
+            assert_eq!(contract.recent_outflows.len(), 1);
+            assert_eq!(contract.recent_outflows[0].amount, 300);
+        }
+
+        #[ink::test]
+        fn test_pot_funding_and_spend() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let policy = PotPolicy { max_single_spend: Some(300), require_steward_approval: false };
+            pass_and_execute(&mut contract, Payload::Pot(PotAction::CreatePot {
+                name: String::from("events"),
+                policy,
+                stewards: vec![],
+            }), true);
+            pass_and_execute(&mut contract, Payload::Pot(PotAction::Transfer {
+                from: None,
+                to: Some(String::from("events")),
+                amount: 600,
+            }), true);
+            
+            // Funds moved into a pot leave the main treasury's available balance
+            assert_eq!(contract.get_available_balance(), 400);
+            assert_eq!(contract.get_pot(String::from("events")).unwrap().balance, 600);
+            
+            let spend = TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 250,
+                asset: Asset::Native,
+                pot: Some(String::from("events")),
+            };
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(spend), false);
+            let stats = contract.get_stats();
+            assert_eq!(stats.pots, vec![PotSummary {
+                name: String::from("events"),
+                balance: 600,
+                liabilities: 250,
+            }]);
+            assert_eq!(contract.get_available_balance(), 400);
+            
+            contract.execute_proposal(proposal_id).unwrap();
+            let pot = contract.get_pot(String::from("events")).unwrap();
+            assert_eq!((pot.balance, pot.liabilities), (350, 0));
+            assert_eq!(contract.get_available_balance(), 400);
+            let claim_id = contract.get_open_claims(accounts.django)[0].id;
+            set_caller(accounts.django);
+            contract.claim(claim_id).unwrap();
+            assert_eq!(contract.get_available_balance(), 400);
+            assert_eq!(ink::env::test::get_contract_balance::<ink::env::DefaultEnvironment>(ink::env::test::callee()).unwrap(), U256::from(750));
+            
+            // The pot's policy caps single spends
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            let result = contract.create_spend_proposal(
+                String::from("Big Event"),
+                String::from("Exceeds the pot's single spend cap"),
+                governance_params,
+                TreasurySpend {
+                    recipient: SpendRecipient::Single(accounts.django),
+                    amount: 301,
+                    asset: Asset::Native,
+                    pot: Some(String::from("events")),
+                },
+            );
+            assert_eq!(result.unwrap_err(), Error::PotPolicyViolation);
+        }
+
+        #[ink::test]
+        fn test_pot_spend_requires_steward_approval() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let policy = PotPolicy { max_single_spend: None, require_steward_approval: true };
+            pass_and_execute(&mut contract, Payload::Pot(PotAction::CreatePot {
+                name: String::from("infra"),
+                policy,
+                stewards: vec![accounts.charlie],
+            }), true);
+            pass_and_execute(&mut contract, Payload::Pot(PotAction::Transfer {
+                from: None,
+                to: Some(String::from("infra")),
+                amount: 500,
+            }), true);
+            
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 200,
+                asset: Asset::Native,
+                pot: Some(String::from("infra")),
+            }), false);
+            assert_eq!(contract.execute_proposal(proposal_id), Err(Error::PotSpendNotApproved));
+            
+            // Only the pot's stewards can sign off
+            set_caller(accounts.bob);
+            assert_eq!(contract.approve_pot_spend(proposal_id), Err(Error::NotAuthorized));
+            set_caller(accounts.charlie);
+            contract.approve_pot_spend(proposal_id).unwrap();
+            
+            contract.execute_proposal(proposal_id).unwrap();
+            assert_eq!(contract.get_pot(String::from("infra")).unwrap().balance, 300);
+        }
+
+        #[ink::test]
+        fn test_bounty_child_awards_and_curator_fee() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let bounty_id = pass_and_execute(&mut contract, Payload::Bounty(500, BountyTerms {
+                description: String::from("Audit the treasury contract"),
+                curator: accounts.bob,
+                curator_fee: 50,
+                duration_blocks: 100_000,
+            }), true);
+            assert_eq!(contract.escrowed_funds, 500);
+            assert_eq!(contract.get_bounty(bounty_id).unwrap().unallocated, 450);
+            
+            // Only the curator splits and awards the bounty
+            assert_eq!(
+                contract.add_child_bounty(bounty_id, String::from("Review"), 300).unwrap_err(),
+                Error::NotAuthorized
+            );
+            set_caller(accounts.bob);
+            let review = contract.add_child_bounty(bounty_id, String::from("Review"), 300).unwrap();
+            let fixes = contract.add_child_bounty(bounty_id, String::from("Fixes"), 150).unwrap();
+            assert_eq!(contract.add_child_bounty(bounty_id, String::from("Extra"), 1), Err(Error::InvalidBounty));
+            contract.award_child_bounty(bounty_id, review, accounts.django).unwrap();
+            
+            // The curator fee waits until every child bounty is awarded
+            assert_eq!(contract.claim_curator_fee(bounty_id), Err(Error::PayoutNotReady));
+            contract.award_child_bounty(bounty_id, fixes, accounts.eve).unwrap();
+            contract.claim_curator_fee(bounty_id).unwrap();
+            
+            // Awards are claimable only after the payout delay
+            assert_eq!(contract.claim_child_bounty(bounty_id, review), Err(Error::PayoutNotReady));
+            let awarded_at = contract.get_bounty(bounty_id).unwrap().children[0].awarded_at.unwrap();
+            set_block_number(awarded_at + BOUNTY_PAYOUT_DELAY);
+            contract.claim_child_bounty(bounty_id, review).unwrap();
+            assert_eq!(contract.claim_child_bounty(bounty_id, review), Err(Error::PayoutNotReady));
+            
+            // The fee and the award are withdrawn through claims
+            let fee = contract.get_open_claims(accounts.bob)[0].clone();
+            assert_eq!((fee.source, fee.amount), (PayoutSource::Proposal(bounty_id), 50));
+            contract.claim(fee.id).unwrap();
+            set_caller(accounts.django);
+            let award = contract.get_open_claims(accounts.django)[0].id;
+            assert_eq!(contract.claim(award), Ok(300));
+            assert_eq!(contract.escrowed_funds, 150);
+            assert_eq!(contract.get_treasury_balance(), 650);
+        }
+
+        #[ink::test]
+        fn test_expired_bounty_returns_unawarded_value() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let bounty_id = pass_and_execute(&mut contract, Payload::Bounty(500, BountyTerms {
+                description: String::from("Write documentation"),
+                curator: accounts.bob,
+                curator_fee: 50,
+                duration_blocks: 100,
+            }), true);
+            set_caller(accounts.bob);
+            let guide = contract.add_child_bounty(bounty_id, String::from("Guide"), 200).unwrap();
+            contract.add_child_bounty(bounty_id, String::from("Reference"), 100).unwrap();
+            contract.award_child_bounty(bounty_id, guide, accounts.django).unwrap();
+            
+            assert_eq!(contract.expire_bounty(bounty_id), Err(Error::BountyStillOpen));
+            let expires_at = contract.get_bounty(bounty_id).unwrap().expires_at;
+            set_block_number(expires_at + 1);
+            
+            // Unallocated value, the unawarded child and the unearned fee go back
+            assert_eq!(contract.expire_bounty(bounty_id).unwrap(), 300);
+            assert_eq!(contract.escrowed_funds, 200);
+            assert_eq!(contract.get_available_balance(), 800);
+            assert_eq!(contract.add_child_bounty(bounty_id, String::from("Late"), 1), Err(Error::BountyExpired));
+            
+            // The awarded child bounty can still be claimed
+            let awarded_at = contract.get_bounty(bounty_id).unwrap().children[0].awarded_at.unwrap();
+            set_block_number(awarded_at + BOUNTY_PAYOUT_DELAY);
+            contract.claim_child_bounty(bounty_id, guide).unwrap();
+            assert_eq!(contract.get_open_claims(accounts.django)[0].amount, 200);
+            assert_eq!(contract.escrowed_funds, 200);
+        }
+
+        #[ink::test]
+        fn test_tip_pays_median_after_countdown() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            contract.council_members = vec![accounts.alice, accounts.bob, accounts.charlie];
+            contract.max_tip = 100;
+            
+            // Only registered voters can nominate
+            set_caller(accounts.eve);
+            assert_eq!(contract.report_tip(Hash::from([1u8; 32]), accounts.django), Err(Error::NotRegisteredVoter));
+            contract.register_voter().unwrap();
+            let tip_id = contract.report_tip(Hash::from([1u8; 32]), accounts.django).unwrap();
+            assert_eq!(contract.tip(tip_id, 50), Err(Error::NotCouncilMember));
+            
+            set_block_number(10);
+            set_caller(accounts.alice);
+            contract.tip(tip_id, 80).unwrap();
+            assert_eq!(contract.close_tip(tip_id), Err(Error::PayoutNotReady));
+            set_caller(accounts.bob);
+            contract.tip(tip_id, 20).unwrap();
+            
+            // A majority has tipped, so the countdown is running
+            assert_eq!(contract.get_tip(tip_id).unwrap().closes_at, Some(10 + TIP_COUNTDOWN));
+            set_caller(accounts.charlie);
+            contract.tip(tip_id, 50).unwrap();
+            assert_eq!(contract.close_tip(tip_id), Err(Error::PayoutNotReady));
+            
+            set_block_number(10 + TIP_COUNTDOWN);
+            assert_eq!(contract.close_tip(tip_id), Ok(50));
+            assert_eq!(contract.get_tip(tip_id).unwrap().paid, Some(50));
+            assert_eq!(contract.close_tip(tip_id), Err(Error::TipClosed));
+            
+            // The tip is withdrawn by the beneficiary
+            let claim = contract.get_open_claims(accounts.django)[0].clone();
+            assert_eq!((claim.source, claim.amount), (PayoutSource::Tip(tip_id), 50));
+            assert_eq!(contract.get_available_balance(), 950);
+            set_caller(accounts.django);
+            assert_eq!(contract.claim(claim.id), Ok(50));
+            assert_eq!(contract.get_treasury_balance(), 950);
+        }
+
+        #[ink::test]
+        fn test_tips_capped_by_governance() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            contract.council_members = vec![accounts.alice, accounts.bob];
+
+            // Tipping is off until governance sets a cap
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let tip_id = contract.report_tip(Hash::from([1u8; 32]), accounts.django).unwrap();
+            assert_eq!(contract.tip(tip_id, 1), Err(Error::TipTooLarge));
+
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetMaxTip(60)]), true);
+            assert_eq!(contract.max_tip, 60);
+            set_caller(accounts.alice);
+            assert_eq!(contract.tip(tip_id, 61), Err(Error::TipTooLarge));
+            contract.tip(tip_id, 60).unwrap();
+            assert_eq!(contract.get_tip(tip_id).unwrap().tips, vec![(accounts.alice, 60)]);
+        }
+
+        #[ink::test]
+        fn test_unclaimed_spend_expires_to_treasury() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 400,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            contract.execute_proposal(proposal_id).unwrap();
+            let claim = contract.get_open_claims(accounts.django)[0].clone();
+            assert_eq!(contract.expire_claim(claim.id), Err(Error::ClaimStillOpen));
+            
+            set_block_number(claim.expires_at + 1);
+            set_caller(accounts.django);
+            assert_eq!(contract.claim(claim.id), Err(Error::ClaimExpired));
+            assert_eq!(contract.expire_claim(claim.id).unwrap(), 400);
+            
+            assert_eq!(contract.get_available_balance(), 1000);
+            assert_eq!(contract.get_claim(claim.id), None);
+            assert!(contract.get_open_claims(accounts.django).is_empty());
+        }
+
+        #[ink::test]
+        fn test_split_spend_opens_claim_per_recipient() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Split(vec![
+                    SpendShare { recipient: accounts.django, portion: SharePortion::BasisPoints(5_000) },
+                    SpendShare { recipient: accounts.eve, portion: SharePortion::BasisPoints(3_000) },
+                    SpendShare { recipient: accounts.frank, portion: SharePortion::BasisPoints(2_000) },
+                ]),
+                amount: 101,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            contract.execute_proposal(proposal_id).unwrap();
+            
+            // Rounding dust goes to the last recipient
+            assert_eq!(contract.get_open_claims(accounts.django)[0].amount, 50);
+            assert_eq!(contract.get_open_claims(accounts.eve)[0].amount, 30);
+            assert_eq!(contract.get_open_claims(accounts.frank)[0].amount, 21);
+            assert_eq!(contract.escrowed_funds, 101);
+            
+            // Each share is withdrawn independently
+            let claim_id = contract.get_open_claims(accounts.eve)[0].id;
+            set_caller(accounts.eve);
+            contract.claim(claim_id).unwrap();
+            assert_eq!(contract.escrowed_funds, 71);
+        }
+
+        #[ink::test]
+        fn test_split_spend_must_add_up() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            let spend_with = |splits: Vec<SpendShare>| TreasurySpend {
+                recipient: SpendRecipient::Split(splits),
+                amount: 100,
+                asset: Asset::Native,
+                pot: None,
+            };
+            
+            let short = spend_with(vec![
+                SpendShare { recipient: accounts.django, portion: SharePortion::Amount(60) },
+                SpendShare { recipient: accounts.eve, portion: SharePortion::Amount(30) },
+            ]);
+            let mixed = spend_with(vec![
+                SpendShare { recipient: accounts.django, portion: SharePortion::Amount(50) },
+                SpendShare { recipient: accounts.eve, portion: SharePortion::BasisPoints(5_000) },
+            ]);
+            for spend in [short, mixed, spend_with(Vec::new())] {
+                let result = contract.create_spend_proposal(
+                    String::from("Team Grant"),
+                    String::from("Pay the team"),
+                    governance_params.clone(),
+                    spend,
+                );
+                assert_eq!(result.unwrap_err(), Error::InvalidSpend);
+            }
+            
+            let exact = spend_with(vec![
+                SpendShare { recipient: accounts.django, portion: SharePortion::Amount(60) },
+                SpendShare { recipient: accounts.eve, portion: SharePortion::Amount(40) },
+            ]);
+            assert!(contract.create_spend_proposal(
+                String::from("Team Grant"),
+                String::from("Pay the team"),
+                governance_params,
+                exact,
+            ).is_ok());
+        }
+
+        #[ink::test]
+        fn test_config_proposal_sets_policy_and_deposit() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetProposalPolicy {
+                    proposal_type: ProposalType::Technical,
+                    policy: Some(ProposalPolicy {
+                        min_voting_period: VotingPeriod::SevenDays,
+                        min_quorum: QuorumThreshold::Ten,
+                        min_execution_delay: ExecutionDelay::OneDay,
+                        deposit: 100,
+                        allow_early_decision: false,
+                    }),
+                },
+                ConfigChange::SetMaxVotingOptions(3),
+            ]), true);
+            
+            let mut governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            let options = VotingOptions {
+                options: vec![String::from("Upgrade"), String::from("Keep")],
+            };
+            let create = |contract: &mut TreasuryGovernance, params: &GovernanceParameters, options: &VotingOptions| {
+                contract.create_proposal(
+                    String::from("Upgrade"),
+                    String::from("Ship the new runtime"),
+                    ProposalType::Technical,
+                    params.clone(),
+                    options.clone(),
+                )
+            };
+            
+            assert_eq!(create(&mut contract, &governance_params, &options), Err(Error::PolicyViolation));
+            governance_params.voting_period = VotingPeriod::SevenDays;
+            assert_eq!(create(&mut contract, &governance_params, &options), Err(Error::InsufficientDeposit));
+            let four_options = VotingOptions {
+                options: vec![String::from("A"), String::from("B"), String::from("C"), String::from("D")],
+            };
+            assert_eq!(create(&mut contract, &governance_params, &four_options), Err(Error::TooManyVotingOptions));
+            
+            // The deposit is held apart from the spendable treasury
+            ink::env::test::set_value_transferred(U256::from(100));
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1100));
+            let proposal_id = create(&mut contract, &governance_params, &options).unwrap();
+            ink::env::test::set_value_transferred(U256::zero());
+            assert_eq!(contract.held_deposits, 100);
+            assert_eq!(contract.get_available_balance(), 1000);
+            
+            // It comes back to the proposer as a claim once voting closes with quorum
+            contract.vote(proposal_id, 0).unwrap();
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.held_deposits, 0);
+            assert_eq!(contract.get_open_claims(accounts.alice)[0].amount, 100);
+        }
+
+        #[ink::test]
+        fn test_config_proposal_closes_membership_and_sets_guardians() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetMembershipMode(MembershipMode::Closed),
+                ConfigChange::AdmitVoters(vec![accounts.bob]),
+                ConfigChange::SetGuardians(vec![accounts.charlie]),
+            ]), true);
+            
+            set_caller(accounts.eve);
+            assert_eq!(contract.register_voter(), Err(Error::RegistrationClosed));
+            assert_eq!(contract.get_total_voters(), 2);
+            
+            // Guardians can pause outflows; governance resumes them without the owner
+            assert_eq!(contract.pause_outflows(), Err(Error::NotAuthorized));
+            set_caller(accounts.charlie);
+            contract.pause_outflows().unwrap();
+            assert!(contract.is_outflow_paused());
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::ResumeOutflows]), true);
+            assert!(!contract.is_outflow_paused());
+        }
+
+        #[ink::test]
+        fn test_config_admit_voters_rejects_duplicates_and_zero_address() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            let duplicate = pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::AdmitVoters(vec![accounts.bob, accounts.bob]),
+            ]), false);
+            assert_eq!(contract.execute_proposal(duplicate), Err(Error::InvalidConfigChange));
+            let zero = pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::AdmitVoters(vec![accounts.bob, H160::zero()]),
+            ]), false);
+            assert_eq!(contract.execute_proposal(zero), Err(Error::InvalidConfigChange));
+            assert_eq!(contract.get_total_voters(), 1);
+        }
+
+        #[ink::test]
+        fn test_config_set_guardians_rejects_duplicates_and_zero_address() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            let duplicate = pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetGuardians(vec![accounts.charlie, accounts.charlie]),
+            ]), false);
+            assert_eq!(contract.execute_proposal(duplicate), Err(Error::InvalidConfigChange));
+            let zero = pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetGuardians(vec![H160::zero()]),
+            ]), false);
+            assert_eq!(contract.execute_proposal(zero), Err(Error::InvalidConfigChange));
+            assert!(contract.guardians.is_empty());
+        }
+
+        #[ink::test]
+        fn test_upgrade_proposal_requires_minimum_delay() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            let code_hash = H256::from([7u8; 32]);
+            
+            let mut governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::Ten,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            assert_eq!(
+                contract.create_upgrade_proposal(
+                    String::from("Upgrade"),
+                    String::from("Fix the tally bug"),
+                    governance_params.clone(),
+                    code_hash,
+                ),
+                Err(Error::UpgradeDelayTooShort)
+            );
+            
+            governance_params.execution_delay = ExecutionDelay::TwoDays;
+            let proposal_id = contract.create_upgrade_proposal(
+                String::from("Upgrade"),
+                String::from("Fix the tally bug"),
+                governance_params,
+                code_hash,
+            ).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().proposal_type, ProposalType::Technical);
+            
+            // The council cannot fast-track past the upgrade delay
+            contract.council_members = vec![accounts.alice];
+            assert_eq!(
+                contract.council_vote(proposal_id, CouncilMotionKind::FastTrack, true),
+                Err(Error::UpgradeDelayTooShort)
+            );
+            
+            contract.vote(proposal_id, 0).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            set_block_number(proposal.voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.execute_proposal(proposal_id), Err(Error::ProposalNotReadyForExecution));
+            set_block_number(proposal.execution_time);
+            contract.execute_proposal(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Executed);
+        }
+
+        #[ink::test]
+        fn test_first_call_after_upgrade_migrates_baseline_proposals() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            assert_eq!(contract.contract_version(), CONTRACT_VERSION);
+            
+            // Storage as the first release left it: no version and proposals in the old encoding
+            let legacy = LegacyProposal {
+                id: 1,
+                title: String::from("Legacy"),
+                description: String::from("Written before storage was versioned"),
+                proposal_type: ProposalType::Other,
+                governance_params: LegacyGovernanceParameters {
+                    voting_period: VotingPeriod::ThreeDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::Immediately,
+                },
+                voting_options: VotingOptions { options: vec![String::from("Yes"), String::from("No")] },
+                proposer: accounts.alice,
+                created_at: 0,
+                voting_end: 1000,
+                execution_time: 1000,
+                status: LegacyProposalStatus::Active,
+                vote_counts: vec![1, 0],
+                total_voters: 1,
+            };
+            let key = ink::storage::traits::StorageKey::key(&contract.proposals);
+            ink::env::set_contract_storage(&(key, 1u32), &legacy);
+            contract.storage_version = 0;
+            contract.next_proposal_id = 2;
+            contract.proposal_ids = vec![1];
+            contract.total_voters = 1;
+            
+            // The first state-changing call migrates before doing its own work
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            assert_eq!(contract.storage_version, CONTRACT_VERSION);
+            let proposal = contract.get_proposal(1).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Active);
+            assert_eq!(proposal.governance_params.tie_policy, TiePolicy::Reject);
+            assert_eq!((proposal.voting_start, proposal.voting_end), (0, 1000));
+            assert_eq!(proposal.vote_counts, vec![1, 0]);
+            
+            // The migrated proposal keeps working under the new code
+            set_block_number(10);
+            contract.vote(1, 1).unwrap();
+            assert_eq!(contract.get_proposal(1).unwrap().vote_counts, vec![1, 1]);
+            contract.migrate().unwrap();
+            assert_eq!(contract.get_proposal(1).unwrap().vote_counts, vec![1, 1]);
+        }
+
+        #[ink::test]
+        fn test_state_export_import_round_trip() {
+            use ink::scale::{Decode, Encode};
+            
+            let mut source = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            let first = create_tied_proposal(&mut source, TiePolicy::Reject, 0);
+            let governance_params = GovernanceParameters {
+                voting_period: VotingPeriod::SevenDays,
+                quorum_threshold: QuorumThreshold::Twenty,
+                execution_delay: ExecutionDelay::OneDay,
+                tie_policy: TiePolicy::Reject,
+                early_decision: false,
+            };
+            let second = source.create_election(
+                String::from("Council Election"),
+                String::from("Pick two"),
+                governance_params,
+                VotingOptions { options: vec![String::from("Bob"), String::from("Charlie"), String::from("Eve")] },
+                2,
+            ).unwrap();
+            source.vote_approval(second, vec![0, 2]).unwrap();
+            source.council_members = vec![accounts.bob, accounts.charlie];
+            source.guardians = vec![accounts.charlie];
+            source.max_tip = 50;
+            source.block_time_ms = 12_000;
+            
+            // An open council motion and a proposal still waiting for a second seconder
+            source.council_vote(second, CouncilMotionKind::FastTrack, true).unwrap();
+            source.sponsorship = Some(SponsorshipConfig {
+                seconds_required: 2,
+                deposit_threshold: None,
+                lapse_blocks: 100_000,
+            });
+            set_caller(accounts.alice);
+            let third = create(&mut source, Payload::Plain).unwrap();
+            set_caller(accounts.bob);
+            source.second_proposal(third).unwrap();
+            assert_eq!(source.get_proposal(third).unwrap().status, ProposalStatus::Proposed);
+            
+            // Settled tip and spend-period records
+            source.tips.insert(0, &Tip {
+                id: 0,
+                reason: Hash::from([1u8; 32]),
+                beneficiary: accounts.django,
+                finder: accounts.alice,
+                tips: vec![(accounts.bob, 10)],
+                closes_at: Some(10),
+                paid: Some(10),
+            });
+            source.next_tip_id = 1;
+            source.spend_reports.insert(0, &SpendPeriodReport {
+                period: 0,
+                processed_at: 100,
+                paid_proposals: Vec::new(),
+                total_paid: 0,
+                burned: 0,
+                deferred_proposals: Vec::new(),
+            });
+            source.spend_period_index = 1;
+            
+            // Export in pages and pass everything through its SCALE encoding
+            let header = source.export_header().unwrap();
+            let mut proposal_pages = Vec::new();
+            for offset in 0..header.proposal_count {
+                proposal_pages.push(source.export_proposals(offset, 1).encode());
+            }
+            let voters = source.export_voters(0, 100).encode();
+            let tips = source.export_tips(0, 100).encode();
+            let reports = source.export_spend_reports(0, 100).encode();
+            let encoded_header = header.encode();
+            
+            // Import into a fresh deployment at another address
+            ink::env::test::set_callee(accounts.frank);
+            set_caller(accounts.django);
+            let mut target = TreasuryGovernance::new_for_import();
+            assert_eq!(target.get_proposal(first), None);
+            
+            // Proposal ids must fall below the imported header's next id
+            let pages: Vec<Vec<ExportedProposal>> = proposal_pages.iter()
+                .map(|page| Vec::<ExportedProposal>::decode(&mut &page[..]).unwrap())
+                .collect();
+            assert_eq!(target.import_proposals(pages[0].clone()), Err(Error::InvalidProposal));
+            target.import_header(StateExportHeader::decode(&mut &encoded_header[..]).unwrap()).unwrap();
+            let mut unknown = pages[0].clone();
+            unknown[0].proposal.id = header.next_proposal_id;
+            assert_eq!(target.import_proposals(unknown), Err(Error::InvalidProposal));
+            
+            target.import_voters(Vec::<H160>::decode(&mut &voters[..]).unwrap()).unwrap();
+            for page in pages {
+                target.import_proposals(page).unwrap();
+            }
+            target.import_tips(Vec::<Tip>::decode(&mut &tips[..]).unwrap()).unwrap();
+            target.import_spend_reports(Vec::<SpendPeriodReport>::decode(&mut &reports[..]).unwrap()).unwrap();
+            target.finish_import().unwrap();
+            assert_eq!(target.import_voters(vec![accounts.eve]), Err(Error::ImportClosed));
+            
+            assert_eq!(target.export_header(), Ok(header));
+            assert_eq!(target.guardians, vec![accounts.charlie]);
+            assert_eq!(target.max_tip, 50);
+            assert_eq!(target.block_time_ms, 12_000);
+            assert_eq!(target.export_proposals(0, 100), source.export_proposals(0, 100));
+            assert_eq!(target.export_voters(0, 100), source.export_voters(0, 100));
+            assert_eq!(target.get_user_vote(first, accounts.alice), source.get_user_vote(first, accounts.alice));
+            assert_eq!(target.get_proposal_seconds(third), vec![accounts.bob]);
+            assert_eq!(
+                target.council_motions.get((second, CouncilMotionKind::FastTrack)).unwrap().ayes,
+                vec![accounts.bob]
+            );
+            assert_eq!(target.export_tips(0, 100), source.export_tips(0, 100));
+            assert_eq!(target.export_spend_reports(0, 100), source.export_spend_reports(0, 100));
+            assert_eq!(target.next_proposal_id, third + 1);
+        }
+
+        #[ink::test]
+        fn test_export_refused_while_payouts_are_open() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(1000));
+            
+            // Passed spends are reserved again on import, but open claims are not carried over
+            let proposal_id = pass_and_execute(&mut contract, Payload::Spend(TreasurySpend {
+                recipient: SpendRecipient::Single(accounts.django),
+                amount: 600,
+                asset: Asset::Native,
+                pot: None,
+            }), false);
+            assert!(contract.export_header().is_ok());
+            contract.execute_proposal(proposal_id).unwrap();
+            assert_eq!(contract.export_header(), Err(Error::OpenFinancialState));
+            
+            let claim_id = contract.get_open_claims(accounts.django)[0].id;
+            set_caller(accounts.django);
+            contract.claim(claim_id).unwrap();
+            assert!(contract.export_header().is_ok());
+            
+            // Unpaid tips block it as well
+            set_caller(accounts.alice);
+            contract.report_tip(Hash::from([1u8; 32]), accounts.django).unwrap();
+            assert_eq!(contract.export_header(), Err(Error::OpenFinancialState));
+        }
+
+        #[ink::test]
+        fn test_import_requires_owner_and_known_version() {
+            let accounts = ink::env::test::default_accounts();
+            set_caller(accounts.django);
+            let mut contract = TreasuryGovernance::new_for_import();
+            
+            let mut header = contract.export_header().unwrap();
+            header.version = EXPORT_FORMAT_VERSION + 1;
+            assert_eq!(contract.import_header(header.clone()), Err(Error::UnsupportedExportVersion));
+            
+            set_caller(accounts.eve);
+            header.version = EXPORT_FORMAT_VERSION;
+            assert_eq!(contract.import_header(header), Err(Error::NotAuthorized));
+            
+            // Nothing else can run until the import is finished
+            set_caller(accounts.alice);
+            assert_eq!(contract.register_voter(), Err(Error::ImportInProgress));
+            assert_eq!(create(&mut contract, Payload::Plain), Err(Error::ImportInProgress));
+            assert_eq!(contract.update_proposal_status(0), Err(Error::ImportInProgress));
+            assert_eq!(
+                contract.edit_proposal(
+                    1,
+                    String::from("Edited"),
+                    String::from("Imported drafts stay as exported"),
+                    VotingOptions { options: vec![String::from("Yes"), String::from("No")] },
+                ),
+                Err(Error::ImportInProgress)
+            );
+            set_caller(accounts.django);
+            contract.finish_import().unwrap();
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            assert_eq!(create(&mut contract, Payload::Plain), Ok(1));
+            
+            // Regular deployments never accept imports
+            set_caller(accounts.django);
+            let mut regular = TreasuryGovernance::new();
+            assert_eq!(regular.import_voters(vec![accounts.eve]), Err(Error::ImportClosed));
+        }
+
+        fn genesis_config() -> GenesisConfig {
+            let accounts = ink::env::test::default_accounts();
+            GenesisConfig {
+                owner: accounts.django,
+                guardians: vec![accounts.charlie],
+                members: vec![accounts.alice, accounts.bob],
+                proposal_policies: vec![(ProposalType::Treasury, ProposalPolicy {
+                    min_voting_period: VotingPeriod::SevenDays,
+                    min_quorum: QuorumThreshold::Ten,
+                    min_execution_delay: ExecutionDelay::Immediately,
+                    deposit: 0,
+                    allow_early_decision: false,
+                })],
+                max_voting_options: 4,
+                block_time_ms: 12_000,
+                membership_mode: MembershipMode::Closed,
+                clock: SchedulingClock::BlockNumber,
+            }
+        }
+
+        #[ink::test]
+        fn test_new_with_config_applies_genesis() {
+            let accounts = ink::env::test::default_accounts();
+            let mut contract = TreasuryGovernance::new_with_config(genesis_config()).unwrap();
+            
+            assert_eq!(contract.owner, accounts.django);
+            assert_eq!(contract.get_total_voters(), 2);
+            assert_eq!(contract.export_voters(0, 10), vec![accounts.alice, accounts.bob]);
+            assert_eq!(contract.export_voters(1, 10), vec![accounts.bob]);
+            assert!(contract.export_voters(2, u32::MAX).is_empty());
+            set_caller(accounts.eve);
+            assert_eq!(contract.register_voter(), Err(Error::RegistrationClosed));
+            
+            // Genesis members can act straight away, under the genesis policy and block time
+            set_caller(accounts.bob);
+            let proposal_id = contract.create_proposal(
+                String::from("Fund Events"),
+                String::from("Budget for meetups"),
+                ProposalType::Treasury,
+                GovernanceParameters {
+                    voting_period: VotingPeriod::SevenDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::Immediately,
+                    tie_policy: TiePolicy::Reject,
+                    early_decision: false,
+                },
+                VotingOptions { options: vec![String::from("Yes"), String::from("No")] },
+            ).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.voting_end - u64::from(proposal.created_at), u64::from(VotingPeriod::SevenDays.to_blocks() / 2));
+            contract.vote(proposal_id, 0).unwrap();
+        }
+
+        #[ink::test]
+        fn test_new_with_config_rejects_bad_input() {
+            let accounts = ink::env::test::default_accounts();
+            
+            let mut config = genesis_config();
+            config.max_voting_options = 1;
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.block_time_ms = 0;
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.members.push(accounts.alice);
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            let duplicate = config.proposal_policies[0].clone();
+            config.proposal_policies.push(duplicate);
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.owner = H160::zero();
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.guardians.push(accounts.charlie);
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.members.push(H160::zero());
+            assert_eq!(TreasuryGovernance::new_with_config(config).err(), Some(Error::InvalidGenesisConfig));
+            
+            let mut config = genesis_config();
+            config.members.clear();
+            assert_eq!(TreasuryGovernance::new_with_config(config.clone()).err(), Some(Error::InvalidGenesisConfig));
+            config.membership_mode = MembershipMode::Open;
+            assert!(TreasuryGovernance::new_with_config(config).is_ok());
+        }
+
+        #[ink::test]
+        fn test_timestamp_clock_schedules_on_block_timestamps() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetClock(SchedulingClock::Timestamp)]), true);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000_000);
+            
+            set_caller(accounts.alice);
+            let proposal_id = contract.create_proposal(
+                String::from("Timed Proposal"),
+                String::from("Scheduled on wall-clock time"),
+                ProposalType::Other,
+                GovernanceParameters {
+                    voting_period: VotingPeriod::ThreeDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::OneDay,
+                    tie_policy: TiePolicy::Reject,
+                    early_decision: false,
+                },
+                VotingOptions { options: vec![String::from("Yes"), String::from("No")] },
+            ).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.clock, SchedulingClock::Timestamp);
+            assert_eq!(proposal.voting_end, 1_000_000 + 3 * 24 * 60 * 60 * 1000);
+            assert_eq!(proposal.execution_time, proposal.voting_end + 24 * 60 * 60 * 1000);
+            
+            // Block production stalling does not end the vote; elapsed time does
+            set_block_number(u32::MAX);
+            contract.vote(proposal_id, 0).unwrap();
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+            
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.voting_end + 1);
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            assert_eq!(contract.vote(proposal_id, 0), Ok(VoteOutcome::VotingClosed(ProposalStatus::Passed)));
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+            
+            assert_eq!(contract.execute_proposal(proposal_id), Err(Error::ProposalNotReadyForExecution));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+            contract.execute_proposal(proposal_id).unwrap();
+        }
+
+        #[ink::test]
+        fn test_draft_proposal_editable_until_voting_starts() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            set_block_number(10);
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            assert_eq!(create(&mut contract, Payload::Draft(10)), Err(Error::InvalidVotingStart));
+            let proposal_id = create(&mut contract, Payload::Draft(100)).unwrap();
+            
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Pending);
+            assert_eq!(proposal.voting_end, 100 + u64::from(VotingPeriod::ThreeDays.to_blocks()));
+            
+            // Only the proposer edits, and only before voting opens
+            let options = VotingOptions {
+                options: vec![String::from("Yes"), String::from("No"), String::from("Abstain")],
+            };
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.edit_proposal(proposal_id, String::from("Final"), String::from("Ready"), options.clone()),
+                Err(Error::NotAuthorized)
+            );
+            set_caller(accounts.alice);
+            contract.edit_proposal(proposal_id, String::from("Final"), String::from("Ready"), options.clone()).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.title, String::from("Final"));
+            assert_eq!(proposal.vote_counts, vec![0, 0, 0]);
+            
+            set_block_number(100);
+            assert_eq!(
+                contract.edit_proposal(proposal_id, String::from("Late"), String::from("Too late"), options),
+                Err(Error::ProposalNotEditable)
+            );
         }
 
         #[ink::test]
-        fn test_voter_registration() {
+        fn test_votes_rejected_before_voting_start() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Register first voter
-            assert!(contract.register_voter().is_ok());
-            assert_eq!(contract.get_total_voters(), 1);
+            set_block_number(10);
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = create(&mut contract, Payload::Draft(100)).unwrap();
             
-            // Register same voter again (should not increase count)
-            assert!(contract.register_voter().is_ok());
-            assert_eq!(contract.get_total_voters(), 1);
+            assert_eq!(contract.vote(proposal_id, 0), Err(Error::VotingNotStarted));
+            
+            // Voting opens on the first call at or after the start
+            set_block_number(100);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
+            contract.vote(proposal_id, 0).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().vote_counts, vec![1, 0]);
         }
 
         #[ink::test]
-        fn test_create_proposal_success() {
+        fn test_seconds_open_proposal_for_voting() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
-            
-            let voting_options = VotingOptions {
-                options: vec![
-                    String::from("Yes"),
-                    String::from("No"),
-                ],
-            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetSponsorship(Some(SponsorshipConfig {
+                seconds_required: 1,
+                deposit_threshold: None,
+                lapse_blocks: 100,
+            }))]), true);
             
-            let result = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal for governance"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            );
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Proposed);
+            assert_eq!(proposal.sponsorship_deadline, Some(1100));
+            assert_eq!(contract.second_proposal(proposal_id), Err(Error::NotAuthorized));
             
-            assert!(result.is_ok());
-            let proposal_id = result.unwrap();
-            assert_eq!(proposal_id, 1);
-            assert_eq!(contract.next_proposal_id, 2);
-            assert_eq!(contract.proposal_ids.len(), 1);
+            // Voting runs for the full period from the moment the proposal is sponsored
+            set_block_number(1050);
+            set_caller(accounts.bob);
+            assert_eq!(contract.second_proposal(proposal_id), Err(Error::NotRegisteredVoter));
+            contract.register_voter().unwrap();
+            contract.second_proposal(proposal_id).unwrap();
             
             let proposal = contract.get_proposal(proposal_id).unwrap();
-            assert_eq!(proposal.title, "Test Proposal");
             assert_eq!(proposal.status, ProposalStatus::Active);
-            assert_eq!(proposal.vote_counts.len(), 2);
+            assert_eq!(proposal.voting_start, 1050);
+            assert_eq!(proposal.voting_end, 1050 + u64::from(VotingPeriod::ThreeDays.to_blocks()));
+            assert_eq!(contract.get_proposal_seconds(proposal_id), vec![accounts.bob]);
+            assert_eq!(contract.second_proposal(proposal_id), Err(Error::NotSponsorable));
+            contract.vote(proposal_id, 0).unwrap();
         }
 
         #[ink::test]
-        fn test_voting_success() {
+        fn test_drafts_wait_for_sponsorship() {
             let mut contract = TreasuryGovernance::new();
-            
-            // Register voter first
+            let accounts = ink::env::test::default_accounts();
+
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetSponsorship(Some(SponsorshipConfig {
+                seconds_required: 1,
+                deposit_threshold: None,
+                lapse_blocks: 100,
+            }))]), true);
+
+            // An unsponsored draft does not open when its voting start arrives
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let unsponsored = create(&mut contract, Payload::Draft(1001)).unwrap();
+            let sponsored = create(&mut contract, Payload::Draft(1200)).unwrap();
+            assert_eq!(contract.get_proposal(unsponsored).unwrap().status, ProposalStatus::Proposed);
+            set_block_number(1001);
+            assert_eq!(contract.vote(unsponsored, 0), Err(Error::ProposalNotActive));
+
+            // Seconding a draft keeps its requested start
+            set_caller(accounts.bob);
             contract.register_voter().unwrap();
+            contract.second_proposal(sponsored).unwrap();
+            let proposal = contract.get_proposal(sponsored).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Pending);
+            assert_eq!(proposal.voting_start, 1200);
+            assert_eq!(contract.vote(sponsored, 0), Err(Error::VotingNotStarted));
+            set_block_number(1200);
+            contract.vote(sponsored, 0).unwrap();
+        }
+
+        #[ink::test]
+        fn test_deposit_sponsors_and_unsponsored_proposals_lapse() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Create proposal
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
-            
-            let voting_options = VotingOptions {
-                options: vec![
-                    String::from("Yes"),
-                    String::from("No"),
-                ],
-            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetSponsorship(Some(SponsorshipConfig {
+                seconds_required: 2,
+                deposit_threshold: Some(500),
+                lapse_blocks: 100,
+            }))]), true);
             
-            let proposal_id = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let sponsored = create(&mut contract, Payload::Plain).unwrap();
+            let lapsing = create(&mut contract, Payload::Plain).unwrap();
             
-            // Vote on proposal
-            let result = contract.vote(proposal_id, 0);
-            assert!(result.is_ok());
+            // A large enough deposit stands in for the missing seconds
+            ink::env::test::set_contract_balance(ink::env::test::callee(), U256::from(600));
+            set_caller(accounts.charlie);
+            ink::env::test::set_value_transferred(U256::from(200));
+            contract.top_up_deposit(sponsored).unwrap();
+            assert_eq!(contract.get_proposal(sponsored).unwrap().status, ProposalStatus::Proposed);
+            set_caller(accounts.bob);
+            ink::env::test::set_value_transferred(U256::from(300));
+            contract.top_up_deposit(sponsored).unwrap();
+            assert_eq!(contract.get_proposal(sponsored).unwrap().status, ProposalStatus::Active);
+            ink::env::test::set_value_transferred(U256::from(100));
+            contract.top_up_deposit(lapsing).unwrap();
+            ink::env::test::set_value_transferred(U256::zero());
+            assert_eq!(contract.held_deposits, 600);
             
-            // Check that the vote was recorded by verifying proposal updates
-            // Note: Individual vote lookup may fail due to test environment account differences
+            // Past the deadline the other proposal can no longer be sponsored and expires, forfeiting its deposit
+            set_block_number(1101);
+            contract.register_voter().unwrap();
+            assert_eq!(contract.second_proposal(lapsing), Err(Error::ProposalLapsed));
+            contract.update_proposal_status(lapsing).unwrap();
+            assert_eq!(contract.get_proposal(lapsing).unwrap().status, ProposalStatus::Expired);
+            assert_eq!(contract.held_deposits, 500);
+            assert!(contract.get_open_claims(accounts.bob).is_empty());
             
-            // Check vote counts updated
-            let proposal = contract.get_proposal(proposal_id).unwrap();
-            assert_eq!(proposal.vote_counts[0], 1);
-            assert_eq!(proposal.vote_counts[1], 0);
-            assert_eq!(proposal.total_voters, 1);
+            // Once voting closes with quorum each contributor gets back their own share
+            contract.vote(sponsored, 0).unwrap();
+            let voting_end = contract.get_proposal(sponsored).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(sponsored).unwrap();
+            assert_eq!(contract.held_deposits, 0);
+            assert_eq!(contract.get_open_claims(accounts.charlie)[0].amount, 200);
+            assert_eq!(contract.get_open_claims(accounts.bob)[0].amount, 300);
+            assert!(contract.get_open_claims(accounts.alice).is_empty());
         }
 
         #[ink::test]
-        fn test_create_proposal_no_voting_options() {
+        fn test_sponsorship_deadline_follows_proposal_clock() {
             let mut contract = TreasuryGovernance::new();
-            
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
-            
-            let voting_options = VotingOptions {
-                options: vec![], // Empty options should fail
-            };
-            
-            let result = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            );
-            
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::NoVotingOptions);
+            let accounts = ink::env::test::default_accounts();
+
+            pass_and_execute(&mut contract, Payload::Config(vec![
+                ConfigChange::SetClock(SchedulingClock::Timestamp),
+                ConfigChange::SetSponsorship(Some(SponsorshipConfig {
+                    seconds_required: 1,
+                    deposit_threshold: None,
+                    lapse_blocks: 100,
+                })),
+            ]), true);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_000_000);
+
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            let deadline = 1_000_000 + 100 * u64::from(DEFAULT_BLOCK_TIME_MS);
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().sponsorship_deadline, Some(deadline));
+
+            // Block production stalling does not lapse the proposal; elapsed time does
+            set_block_number(u32::MAX);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Proposed);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(deadline + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Expired);
         }
 
         #[ink::test]
-        fn test_create_proposal_too_many_options() {
+        fn test_late_vote_changing_winner_extends_voting() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetAntiSniping(Some(AntiSnipingConfig {
+                window_blocks: 100,
+                extension_blocks: 50,
+                max_extensions: 1,
+            }))]), true);
             
-            // Create 11 options (should fail as max is 10)
-            let mut options = Vec::new();
-            for i in 0..11 {
-                options.push(format!("Option {}", i));
-            }
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            let execution_time = contract.get_proposal(proposal_id).unwrap().execution_time;
             
-            let voting_options = VotingOptions { options };
+            // Changing the winner well before the window leaves the schedule alone
+            contract.vote(proposal_id, 0).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().voting_end, voting_end);
             
-            let result = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            );
+            // A late tie changes the outcome and extends voting
+            set_block_number(voting_end - 10);
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 1).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.voting_end, voting_end + 50);
+            assert_eq!(proposal.execution_time, execution_time + 50);
+            assert_eq!(proposal.voting_extensions, vec![voting_end - 10]);
             
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::TooManyVotingOptions);
+            // The cap stops further extensions
+            set_caller(accounts.charlie);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 1).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().voting_end, voting_end + 50);
         }
 
         #[ink::test]
-        fn test_vote_unregistered_voter() {
+        fn test_first_vote_in_window_does_not_extend() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Create proposal without registering voter
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
-            
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetAntiSniping(Some(AntiSnipingConfig {
+                window_blocks: 100,
+                extension_blocks: 50,
+                max_extensions: 3,
+            }))]), true);
             
-            let proposal_id = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
             
-            // Try to vote without being registered
-            let result = contract.vote(proposal_id, 0);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::NotRegisteredVoter);
+            // There was no winner to overturn
+            set_block_number(voting_end - 10);
+            contract.vote(proposal_id, 0).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.voting_end, voting_end);
+            assert!(proposal.voting_extensions.is_empty());
         }
 
         #[ink::test]
-        fn test_vote_nonexistent_proposal() {
+        fn test_late_vote_keeping_winner_does_not_extend() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Register voter
-            contract.register_voter().unwrap();
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetAntiSniping(Some(AntiSnipingConfig {
+                window_blocks: 100,
+                extension_blocks: 50,
+                max_extensions: 3,
+            }))]), true);
             
-            // Try to vote on non-existent proposal
-            let result = contract.vote(999, 0);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::ProposalNotFound);
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            
+            set_block_number(voting_end - 10);
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.voting_end, voting_end);
+            assert!(proposal.voting_extensions.is_empty());
         }
 
         #[ink::test]
-        fn test_vote_invalid_option_index() {
+        fn test_confirmed_proposal_passes_before_voting_end() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Register voter
-            contract.register_voter().unwrap();
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetConfirmPeriod(Some(100))]), true);
             
-            // Create proposal with 2 options
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            let execution_delay = proposal.execution_time - proposal.voting_end;
             
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            contract.vote(proposal_id, 0).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Confirming);
+            assert_eq!(proposal.confirm_start, Some(1000));
             
-            let proposal_id = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            set_block_number(1099);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Confirming);
             
-            // Try to vote with invalid option index (2 when only 0,1 exist)
-            let result = contract.vote(proposal_id, 2);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::InvalidOptionIndex);
+            set_block_number(1100);
+            contract.update_proposal_status(proposal_id).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Passed);
+            assert_eq!(proposal.voting_end, 1100);
+            assert_eq!(proposal.execution_time, 1100 + execution_delay);
         }
 
         #[ink::test]
-        fn test_double_voting_same_voter() {
+        fn test_confirmation_restarts_when_support_dips() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Register voter
-            contract.register_voter().unwrap();
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetConfirmPeriod(Some(100))]), true);
             
-            // Create proposal
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
             
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            // A tie loses approval and drops the proposal back to active
+            set_block_number(1050);
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 1).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Active);
+            assert_eq!(proposal.confirm_start, None);
             
-            let proposal_id = contract.create_proposal(
-                String::from("Test Proposal"),
-                String::from("A test proposal"),
-                ProposalType::Treasury,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            set_block_number(1080);
+            set_caller(accounts.charlie);
+            contract.register_voter().unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().confirm_start, Some(1080));
             
-            // First vote should succeed
-            let result = contract.vote(proposal_id, 0);
-            assert!(result.is_ok());
+            set_block_number(1100);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Confirming);
             
-            // Second vote should fail (already voted)
-            let result = contract.vote(proposal_id, 1);
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err(), Error::AlreadyVoted);
+            // Past voting_end the proposal finalizes normally
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
         }
 
         #[ink::test]
-        fn test_get_proposal_details() {
+        fn test_leading_nay_does_not_confirm() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::ThirtyDays,
-                quorum_threshold: QuorumThreshold::TwentyFive,
-                execution_delay: ExecutionDelay::SevenDays,
-            };
-            
-            let voting_options = VotingOptions {
-                options: vec![
-                    String::from("Approve"),
-                    String::from("Reject"),
-                    String::from("Abstain"),
-                ],
-            };
-            
-            let proposal_id = contract.create_proposal(
-                String::from("Treasury Funding"),
-                String::from("Fund development project"),
-                ProposalType::Treasury,
-                governance_params.clone(),
-                voting_options.clone(),
-            ).unwrap();
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetConfirmPeriod(Some(100))]), true);
             
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            let proposal_id = create(&mut contract, Payload::Config(vec![ConfigChange::SetMaxTip(10)])).unwrap();
+            contract.vote(proposal_id, 1).unwrap();
             let proposal = contract.get_proposal(proposal_id).unwrap();
-            assert_eq!(proposal.title, "Treasury Funding");
-            assert_eq!(proposal.description, "Fund development project");
-            assert_eq!(proposal.proposal_type, ProposalType::Treasury);
-            assert_eq!(proposal.governance_params.voting_period, VotingPeriod::ThirtyDays);
-            assert_eq!(proposal.governance_params.quorum_threshold, QuorumThreshold::TwentyFive);
-            assert_eq!(proposal.governance_params.execution_delay, ExecutionDelay::SevenDays);
-            assert_eq!(proposal.voting_options.options.len(), 3);
             assert_eq!(proposal.status, ProposalStatus::Active);
+            assert_eq!(proposal.confirm_start, None);
+            
+            set_block_number(1100);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
         }
 
         #[ink::test]
-        fn test_get_nonexistent_proposal() {
-            let contract = TreasuryGovernance::new();
+        fn test_multi_option_proposal_confirms_on_leading_option() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
+            
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetConfirmPeriod(Some(100))]), true);
+            
+            set_block_number(1000);
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = contract.create_proposal(
+                String::from("Venue"),
+                String::from("Pick the venue"),
+                ProposalType::Other,
+                GovernanceParameters {
+                    voting_period: VotingPeriod::ThreeDays,
+                    quorum_threshold: QuorumThreshold::Ten,
+                    execution_delay: ExecutionDelay::Immediately,
+                    tie_policy: TiePolicy::Reject,
+                    early_decision: false,
+                },
+                VotingOptions { options: vec![String::from("Lisbon"), String::from("Berlin"), String::from("Oslo")] },
+            ).unwrap();
+            contract.vote(proposal_id, 2).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().confirm_start, Some(1000));
             
-            let result = contract.get_proposal(999);
-            assert!(result.is_none());
+            set_block_number(1100);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
+            assert_eq!(contract.get_winning_option(proposal_id).unwrap().map(|(index, _, _)| index), Some(2));
         }
 
         #[ink::test]
-        fn test_multiple_proposal_types() {
+        fn test_settled_outcome_finalizes_early() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
+            set_block_number(10);
+            for voter in [accounts.bob, accounts.charlie, accounts.alice] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+            }
+            let proposal_id = create(&mut contract, Payload::EarlyDecision).unwrap();
+            let opted_out = create(&mut contract, Payload::Plain).unwrap();
             
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            // One vote ahead with one voter left is still open
+            contract.vote(proposal_id, 0).unwrap();
+            contract.vote(opted_out, 0).unwrap();
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Active);
             
-            // Create different types of proposals
-            let treasury_id = contract.create_proposal(
-                String::from("Treasury Proposal"),
-                String::from("Treasury funding"),
-                ProposalType::Treasury,
-                governance_params.clone(),
-                voting_options.clone(),
-            ).unwrap();
+            set_block_number(20);
+            set_caller(accounts.bob);
+            contract.vote(proposal_id, 0).unwrap();
+            contract.vote(opted_out, 0).unwrap();
+            contract.update_proposal_status(proposal_id).unwrap();
+            contract.update_proposal_status(opted_out).unwrap();
             
-            let governance_id = contract.create_proposal(
-                String::from("Governance Proposal"),
-                String::from("Change governance rules"),
-                ProposalType::Governance,
-                governance_params.clone(),
-                voting_options.clone(),
-            ).unwrap();
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Passed);
+            assert_eq!(proposal.voting_end, 20);
+            assert_eq!(contract.get_proposal(opted_out).unwrap().status, ProposalStatus::Active);
+        }
+
+        #[ink::test]
+        fn test_tie_with_no_voters_left_rejects_early() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let technical_id = contract.create_proposal(
-                String::from("Technical Proposal"),
-                String::from("Technical upgrade"),
-                ProposalType::Technical,
-                governance_params.clone(),
-                voting_options.clone(),
-            ).unwrap();
+            set_block_number(10);
+            for voter in [accounts.bob, accounts.charlie, accounts.django, accounts.alice] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+            }
+            let proposal_id = create(&mut contract, Payload::EarlyDecision).unwrap();
             
-            let other_id = contract.create_proposal(
-                String::from("Other Proposal"),
-                String::from("Other type"),
-                ProposalType::Other,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            // Every registered voter has voted without a winner, so nothing can change
+            contract.vote(proposal_id, 0).unwrap();
+            set_caller(accounts.bob);
+            contract.vote(proposal_id, 1).unwrap();
+            set_caller(accounts.charlie);
+            contract.vote(proposal_id, 0).unwrap();
+            set_caller(accounts.django);
+            contract.vote(proposal_id, 1).unwrap();
             
-            // Verify all proposals were created with correct types
-            assert_eq!(contract.get_proposal(treasury_id).unwrap().proposal_type, ProposalType::Treasury);
-            assert_eq!(contract.get_proposal(governance_id).unwrap().proposal_type, ProposalType::Governance);
-            assert_eq!(contract.get_proposal(technical_id).unwrap().proposal_type, ProposalType::Technical);
-            assert_eq!(contract.get_proposal(other_id).unwrap().proposal_type, ProposalType::Other);
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Rejected);
+        }
+
+        #[ink::test]
+        fn test_early_decision_limits_votes_to_snapshot_electorate() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            assert_eq!(contract.proposal_ids.len(), 4);
+            set_block_number(10);
+            for voter in [accounts.bob, accounts.alice] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+            }
+            let proposal_id = create(&mut contract, Payload::EarlyDecision).unwrap();
+            let opted_out = create(&mut contract, Payload::Plain).unwrap();
+            
+            // A voter registered after creation cannot swing an early-decision proposal
+            set_caller(accounts.charlie);
+            contract.register_voter().unwrap();
+            assert_eq!(contract.vote(proposal_id, 1), Err(Error::RegisteredAfterProposal));
+            contract.vote(opted_out, 1).unwrap();
+            
+            // Both snapshot voters agreeing settles it
+            set_caller(accounts.alice);
+            contract.vote(proposal_id, 0).unwrap();
+            set_caller(accounts.bob);
+            contract.vote(proposal_id, 0).unwrap();
+            contract.update_proposal_status(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Passed);
         }
 
         #[ink::test]
-        fn test_get_all_proposal_ids() {
+        fn test_early_decision_quorum_counts_snapshot_electorate() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
+            set_block_number(10);
+            for voter in [accounts.bob, accounts.charlie, accounts.django, accounts.alice] {
+                set_caller(voter);
+                contract.register_voter().unwrap();
+            }
             let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
+                voting_period: VotingPeriod::ThreeDays,
+                quorum_threshold: QuorumThreshold::TwentyFive,
+                execution_delay: ExecutionDelay::Immediately,
+                tie_policy: TiePolicy::Reject,
+                early_decision: true,
             };
-            
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
+            let create_with = |contract: &mut TreasuryGovernance, params: GovernanceParameters| {
+                contract.create_proposal(
+                    String::from("Snapshot Quorum"),
+                    String::from("Quorum from the voters at creation"),
+                    ProposalType::Other,
+                    params,
+                    VotingOptions { options: vec![String::from("Yes"), String::from("No")] },
+                ).unwrap()
             };
+            let proposal_id = create_with(&mut contract, governance_params.clone());
+            let opted_out = create_with(&mut contract, GovernanceParameters { early_decision: false, ..governance_params });
+            contract.vote(proposal_id, 0).unwrap();
+            contract.vote(opted_out, 0).unwrap();
             
-            // Initially no proposals
-            assert_eq!(contract.get_all_proposal_ids().len(), 0);
-            
-            // Create 3 proposals
-            for i in 1..=3 {
-                contract.create_proposal(
-                    format!("Proposal {}", i),
-                    format!("Description {}", i),
-                    ProposalType::Treasury,
-                    governance_params.clone(),
-                    voting_options.clone(),
-                ).unwrap();
+            // Later registrations cannot vote on it, so they do not raise its quorum
+            for seed in 1..=8u8 {
+                set_caller(H160::from([seed; 20]));
+                contract.register_voter().unwrap();
             }
-            
-            let proposal_ids = contract.get_all_proposal_ids();
-            assert_eq!(proposal_ids.len(), 3);
-            assert_eq!(proposal_ids, vec![1, 2, 3]);
+            assert!(contract.get_proposal_results(proposal_id).unwrap().1);
+            assert!(!contract.get_proposal_results(opted_out).unwrap().1);
         }
 
         #[ink::test]
-        fn test_contract_stats() {
+        fn test_policy_decides_which_types_may_decide_early() {
             let mut contract = TreasuryGovernance::new();
             
-            // Initially no proposals or voters
-            let stats = contract.get_stats();
-            assert_eq!(stats.total_proposals, 0);
-            assert_eq!(stats.total_voters, 0);
-            assert_eq!(stats.active_proposals, 0);
-            assert_eq!(stats.executed_proposals, 0);
-            
-            // Register voters
-            contract.register_voter().unwrap();
-            
-            // Create proposals
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
+            let policy = ProposalPolicy {
+                min_voting_period: VotingPeriod::ThreeDays,
+                min_quorum: QuorumThreshold::Ten,
+                min_execution_delay: ExecutionDelay::Immediately,
+                deposit: 0,
+                allow_early_decision: false,
             };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetProposalPolicy {
+                proposal_type: ProposalType::Other,
+                policy: Some(policy.clone()),
+            }]), true);
+            assert_eq!(create(&mut contract, Payload::EarlyDecision), Err(Error::PolicyViolation));
+            assert!(create(&mut contract, Payload::Plain).is_ok());
             
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            pass_and_execute(&mut contract, Payload::Config(vec![ConfigChange::SetProposalPolicy {
+                proposal_type: ProposalType::Other,
+                policy: Some(ProposalPolicy { allow_early_decision: true, ..policy }),
+            }]), true);
+            assert!(create(&mut contract, Payload::EarlyDecision).is_ok());
+        }
+
+        #[ink::test]
+        fn test_late_vote_settles_proposal_without_an_error() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            contract.create_proposal(
-                String::from("Proposal 1"),
-                String::from("Description 1"),
-                ProposalType::Treasury,
-                governance_params.clone(),
-                voting_options.clone(),
-            ).unwrap();
+            set_block_number(10);
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
+            let fast_tracked = create(&mut contract, Payload::Plain).unwrap();
+            contract.council_members = vec![accounts.alice];
+            set_caller(accounts.bob);
+            contract.register_voter().unwrap();
             
-            contract.create_proposal(
-                String::from("Proposal 2"),
-                String::from("Description 2"),
-                ProposalType::Governance,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            // An error would revert the settlement, so the late vote reports it instead
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            assert_eq!(contract.vote(proposal_id, 1), Ok(VoteOutcome::VotingClosed(ProposalStatus::Passed)));
+            let proposal = contract.get_proposal(proposal_id).unwrap();
+            assert_eq!(proposal.status, ProposalStatus::Passed);
+            assert_eq!(proposal.vote_counts, vec![1, 0]);
+            assert_eq!(contract.vote(proposal_id, 1), Err(Error::ProposalNotActive));
             
-            let stats = contract.get_stats();
-            assert_eq!(stats.total_proposals, 2);
-            assert_eq!(stats.total_voters, 1);
-            assert_eq!(stats.active_proposals, 2);
-            assert_eq!(stats.executed_proposals, 0);
+            set_caller(accounts.alice);
+            assert_eq!(
+                contract.council_vote(fast_tracked, CouncilMotionKind::FastTrack, true),
+                Ok(VoteOutcome::VotingClosed(ProposalStatus::Rejected))
+            );
+            assert_eq!(contract.get_proposal(fast_tracked).unwrap().status, ProposalStatus::Rejected);
+            assert_eq!(
+                contract.council_vote(fast_tracked, CouncilMotionKind::FastTrack, true),
+                Err(Error::ProposalNotActive)
+            );
         }
 
         #[ink::test]
-        fn test_multi_option_voting() {
+        fn test_execute_finalizes_ended_proposal() {
             let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            // Register voter
+            set_block_number(10);
+            set_caller(accounts.alice);
             contract.register_voter().unwrap();
+            let proposal_id = create(&mut contract, Payload::Plain).unwrap();
+            contract.vote(proposal_id, 0).unwrap();
             
-            // Create proposal with multiple options
-            let governance_params = GovernanceParameters {
-                voting_period: VotingPeriod::SevenDays,
-                quorum_threshold: QuorumThreshold::Ten,
-                execution_delay: ExecutionDelay::OneDay,
-            };
+            // No explicit status update is needed once voting has ended
+            let voting_end = contract.get_proposal(proposal_id).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            contract.execute_proposal(proposal_id).unwrap();
+            assert_eq!(contract.get_proposal(proposal_id).unwrap().status, ProposalStatus::Executed);
+        }
+
+        #[ink::test]
+        fn test_finalize_batch_settles_ended_proposals() {
+            let mut contract = TreasuryGovernance::new();
+            let accounts = ink::env::test::default_accounts();
             
-            let voting_options = VotingOptions {
-                options: vec![
-                    String::from("Option A"),
-                    String::from("Option B"),
-                    String::from("Option C"),
-                    String::from("Option D"),
-                ],
-            };
+            set_block_number(10);
+            set_caller(accounts.alice);
+            contract.register_voter().unwrap();
+            let passing = create(&mut contract, Payload::Plain).unwrap();
+            let failing = create(&mut contract, Payload::Plain).unwrap();
+            contract.vote(passing, 0).unwrap();
             
-            let proposal_id = contract.create_proposal(
-                String::from("Multi-Option Proposal"),
-                String::from("Choose from multiple options"),
-                ProposalType::Governance,
-                governance_params,
-                voting_options,
-            ).unwrap();
+            set_block_number(20);
+            let still_open = create(&mut contract, Payload::Plain).unwrap();
             
-            // Vote for option C (index 2)
-            let result = contract.vote(proposal_id, 2);
-            assert!(result.is_ok());
+            let voting_end = contract.get_proposal(passing).unwrap().voting_end;
+            set_block_number(voting_end + 1);
             
-            // Check vote was recorded correctly
-            let proposal = contract.get_proposal(proposal_id).unwrap();
-            assert_eq!(proposal.vote_counts[0], 0); // Option A
-            assert_eq!(proposal.vote_counts[1], 0); // Option B
-            assert_eq!(proposal.vote_counts[2], 1); // Option C
-            assert_eq!(proposal.vote_counts[3], 0); // Option D
-            assert_eq!(proposal.total_voters, 1);
+            // Unknown ids are reported without failing the rest of the batch
+            assert_eq!(
+                contract.finalize_batch(vec![passing, 99, failing, still_open]),
+                Ok(vec![
+                    FinalizeOutcome::Settled(ProposalStatus::Passed),
+                    FinalizeOutcome::NotFound,
+                    FinalizeOutcome::Settled(ProposalStatus::Rejected),
+                    FinalizeOutcome::Unchanged(ProposalStatus::Active),
+                ])
+            );
+            assert_eq!(contract.get_proposal(passing).unwrap().status, ProposalStatus::Passed);
+            assert_eq!(contract.get_proposal(failing).unwrap().status, ProposalStatus::Rejected);
+            assert_eq!(contract.get_proposal(still_open).unwrap().status, ProposalStatus::Active);
+            
+            assert_eq!(contract.finalize_batch(vec![passing; MAX_FINALIZE_BATCH + 1]), Err(Error::BatchTooLarge));
         }
 
         #[ink::test]
-        fn test_different_governance_parameters() {
+        fn test_finalize_batch_defers_elections_past_ballot_budget() {
             let mut contract = TreasuryGovernance::new();
+            contract.register_voter().unwrap();
             
-            // Test different voting periods
-            let short_params = GovernanceParameters {
+            let mut elections = Vec::new();
+            for _ in 0..2 {
+                elections.push(contract.create_election(
+                    String::from("Council Election"),
+                    String::from("Elect one council member"),
+                    GovernanceParameters {
+                        voting_period: VotingPeriod::SevenDays,
+                        quorum_threshold: QuorumThreshold::Ten,
+                        execution_delay: ExecutionDelay::OneDay,
+                        tie_policy: TiePolicy::Reject,
+                        early_decision: false,
+                    },
+                    VotingOptions { options: vec![String::from("Alice"), String::from("Bob")] },
+                    1,
+                ).unwrap());
+            }
+            for index in 0..MAX_FINALIZE_BATCH_BALLOTS / 2 + 1 {
+                set_caller(H160::from_low_u64_be(u64::from(index) + 1));
+                contract.register_voter().unwrap();
+                for &election in &elections {
+                    contract.vote_approval(election, vec![0]).unwrap();
+                }
+            }
+            
+            // Both tallies together exceed the budget, so the second waits for another call
+            let voting_end = contract.get_proposal(elections[0]).unwrap().voting_end;
+            set_block_number(voting_end + 1);
+            assert_eq!(
+                contract.finalize_batch(elections.clone()),
+                Ok(vec![FinalizeOutcome::Settled(ProposalStatus::Passed), FinalizeOutcome::Deferred])
+            );
+            assert_eq!(contract.get_proposal(elections[1]).unwrap().status, ProposalStatus::Active);
+            assert_eq!(
+                contract.finalize_batch(elections),
+                Ok(vec![
+                    FinalizeOutcome::Unchanged(ProposalStatus::Passed),
+                    FinalizeOutcome::Settled(ProposalStatus::Passed),
+                ])
+            );
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink::prelude::vec;
+        use ink_e2e::ContractsBackend;
+        use mock_psp22::{MockPsp22, MockPsp22Ref, PSP22};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+        type E2EClient = ink_e2e::Client<ink_e2e::PolkadotConfig, ink::env::DefaultEnvironment>;
+
+        /// Deploys the treasury, owned by Alice and with her as its only voter, next to a fresh token
+        async fn deploy(client: &mut E2EClient) -> (H160, H160) {
+            let treasury = client
+                .instantiate("treasury_governance", &ink_e2e::alice(), &mut TreasuryGovernanceRef::new())
+                .submit()
+                .await
+                .expect("treasury instantiate failed")
+                .addr;
+            let token = client
+                .instantiate("mock_psp22", &ink_e2e::alice(), &mut MockPsp22Ref::new())
+                .submit()
+                .await
+                .expect("token instantiate failed")
+                .addr;
+            
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            client
+                .call(&ink_e2e::alice(), &calls.register_voter())
+                .submit()
+                .await
+                .expect("register_voter failed");
+            (treasury, token)
+        }
+
+        fn governance_params() -> GovernanceParameters {
+            GovernanceParameters {
                 voting_period: VotingPeriod::ThreeDays,
-                quorum_threshold: QuorumThreshold::Five,
+                quorum_threshold: QuorumThreshold::Ten,
                 execution_delay: ExecutionDelay::Immediately,
-            };
+                tie_policy: TiePolicy::Reject,
+                // Lets the sole voter settle the outcome without waiting out the period
+                early_decision: true,
+            }
+        }
+
+        /// Creates a spend proposal and passes it on Alice's vote alone
+        async fn pass_spend(client: &mut E2EClient, treasury: H160, spend: TreasurySpend) -> u32 {
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            let proposal_id = client
+                .call(&ink_e2e::alice(), &calls.create_spend_proposal(
+                    String::from("Grant"),
+                    String::from("Fund a contributor"),
+                    governance_params(),
+                    spend,
+                ))
+                .submit()
+                .await
+                .expect("create_spend_proposal failed")
+                .return_value()
+                .expect("spend proposal rejected");
+            pass(client, treasury, proposal_id).await;
+            proposal_id
+        }
+
+        /// Passes and executes a config proposal on Alice's vote alone
+        async fn apply_config(client: &mut E2EClient, treasury: H160, changes: Vec<ConfigChange>) {
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            let proposal_id = client
+                .call(&ink_e2e::alice(), &calls.create_config_proposal(
+                    String::from("Config Change"),
+                    String::from("Update the DAO's rules"),
+                    governance_params(),
+                    changes,
+                ))
+                .submit()
+                .await
+                .expect("create_config_proposal failed")
+                .return_value()
+                .expect("config proposal rejected");
+            pass(client, treasury, proposal_id).await;
+            client
+                .call(&ink_e2e::alice(), &calls.execute_proposal(proposal_id))
+                .submit()
+                .await
+                .expect("execute_proposal failed");
+        }
+
+        /// Settles a proposal on Alice's vote alone
+        async fn pass(client: &mut E2EClient, treasury: H160, proposal_id: u32) {
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            client
+                .call(&ink_e2e::alice(), &calls.vote(proposal_id, 0))
+                .submit()
+                .await
+                .expect("vote failed");
+            client
+                .call(&ink_e2e::alice(), &calls.update_proposal_status(proposal_id))
+                .submit()
+                .await
+                .expect("update_proposal_status failed");
+        }
+
+        #[ink_e2e::test]
+        async fn test_psp22_deposits_and_balances(mut client: E2EClient) -> E2EResult<()> {
+            let (treasury, token) = deploy(&mut client).await;
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            let mut token_calls = ink_e2e::create_call_builder::<MockPsp22>(token);
+            let bob = ink_e2e::address::<ink::env::DefaultEnvironment>(ink_e2e::Sr25519Keyring::Bob);
             
-            let long_params = GovernanceParameters {
-                voting_period: VotingPeriod::ThirtyDays,
-                quorum_threshold: QuorumThreshold::TwentyFive,
-                execution_delay: ExecutionDelay::SevenDays,
-            };
+            client.call(&ink_e2e::alice(), &token_calls.mint(bob, 1000)).submit().await?;
+            client.call(&ink_e2e::bob(), &token_calls.approve(treasury, 500)).submit().await?;
             
-            let voting_options = VotingOptions {
-                options: vec![String::from("Yes"), String::from("No")],
-            };
+            // Only tokens accepted by governance can be deposited
+            let rejected = client.call(&ink_e2e::bob(), &calls.deposit_asset(token, 500)).dry_run().await?;
+            assert_eq!(rejected.return_value(), Err(Error::AssetNotAccepted));
+            apply_config(&mut client, treasury, vec![ConfigChange::AddAcceptedAsset(token)]).await;
+            let accepted = client.call(&ink_e2e::bob(), &calls.get_accepted_assets()).dry_run().await?;
+            assert_eq!(accepted.return_value(), vec![token]);
             
-            let short_proposal = contract.create_proposal(
-                String::from("Short Proposal"),
-                String::from("Quick decision"),
-                ProposalType::Technical,
-                short_params,
-                voting_options.clone(),
-            ).unwrap();
+            client.call(&ink_e2e::bob(), &calls.deposit_asset(token, 500)).submit().await?;
+            let balance = client.call(&ink_e2e::bob(), &calls.get_asset_balance(Asset::Psp22(token))).dry_run().await?;
+            assert_eq!(balance.return_value(), Ok(500));
+            let bob_balance = client.call(&ink_e2e::bob(), &token_calls.balance_of(bob)).dry_run().await?;
+            assert_eq!(bob_balance.return_value(), 500);
             
-            let long_proposal = contract.create_proposal(
-                String::from("Long Proposal"),
-                String::from("Important decision"),
-                ProposalType::Treasury,
-                long_params,
-                voting_options,
-            ).unwrap();
+            // Deposits beyond the approved allowance fail
+            let rejected = client.call(&ink_e2e::bob(), &calls.deposit_asset(token, 1)).dry_run().await?;
+            assert_eq!(rejected.return_value(), Err(Error::TokenTransferFailed));
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn test_psp22_spend_executed_via_transfer(mut client: E2EClient) -> E2EResult<()> {
+            let (treasury, token) = deploy(&mut client).await;
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            let mut token_calls = ink_e2e::create_call_builder::<MockPsp22>(token);
+            let bob = ink_e2e::address::<ink::env::DefaultEnvironment>(ink_e2e::Sr25519Keyring::Bob);
             
-            // Verify parameters were set correctly
-            let short_prop = contract.get_proposal(short_proposal).unwrap();
-            assert_eq!(short_prop.governance_params.voting_period, VotingPeriod::ThreeDays);
-            assert_eq!(short_prop.governance_params.quorum_threshold, QuorumThreshold::Five);
-            assert_eq!(short_prop.governance_params.execution_delay, ExecutionDelay::Immediately);
+            apply_config(&mut client, treasury, vec![ConfigChange::AddAcceptedAsset(token)]).await;
+            client.call(&ink_e2e::alice(), &token_calls.mint(treasury, 1000)).submit().await?;
             
-            let long_prop = contract.get_proposal(long_proposal).unwrap();
-            assert_eq!(long_prop.governance_params.voting_period, VotingPeriod::ThirtyDays);
-            assert_eq!(long_prop.governance_params.quorum_threshold, QuorumThreshold::TwentyFive);
-            assert_eq!(long_prop.governance_params.execution_delay, ExecutionDelay::SevenDays);
+            let proposal_id = pass_spend(&mut client, treasury, TreasurySpend {
+                recipient: SpendRecipient::Single(bob),
+                amount: 600,
+                asset: Asset::Psp22(token),
+                pot: None,
+            }).await;
+            
+            // Token liabilities are tracked apart from the native balance
+            let available = client.call(&ink_e2e::alice(), &calls.get_available_asset_balance(Asset::Psp22(token))).dry_run().await?;
+            assert_eq!(available.return_value(), Ok(400));
+            
+            let executed = client.call(&ink_e2e::alice(), &calls.execute_proposal(proposal_id)).submit().await?;
+            assert_eq!(executed.return_value(), Ok(()));
+            let claims = client.call(&ink_e2e::bob(), &calls.get_open_claims(bob)).dry_run().await?;
+            let claim_id = claims.return_value()[0].id;
+            client.call(&ink_e2e::bob(), &calls.claim(claim_id)).submit().await?;
+            
+            let bob_balance = client.call(&ink_e2e::bob(), &token_calls.balance_of(bob)).dry_run().await?;
+            assert_eq!(bob_balance.return_value(), 600);
+            let balance = client.call(&ink_e2e::alice(), &calls.get_asset_balance(Asset::Psp22(token))).dry_run().await?;
+            assert_eq!(balance.return_value(), Ok(400));
+            let available = client.call(&ink_e2e::alice(), &calls.get_available_asset_balance(Asset::Psp22(token))).dry_run().await?;
+            assert_eq!(available.return_value(), Ok(400));
+            
+            // Spends in unregistered tokens are rejected up front
+            let rejected = client
+                .call(&ink_e2e::alice(), &calls.create_spend_proposal(
+                    String::from("Unknown Token Grant"),
+                    String::from("Pays in a token the treasury does not hold"),
+                    governance_params(),
+                    TreasurySpend {
+                        recipient: SpendRecipient::Single(bob),
+                        amount: 1,
+                        asset: Asset::Psp22(H160::from([0x43; 20])),
+                        pot: None,
+                    },
+                ))
+                .dry_run()
+                .await?;
+            assert_eq!(rejected.return_value(), Err(Error::AssetNotAccepted));
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn test_psp22_spends_count_against_token_budget(mut client: E2EClient) -> E2EResult<()> {
+            let (treasury, token) = deploy(&mut client).await;
+            let mut calls = ink_e2e::create_call_builder::<TreasuryGovernance>(treasury);
+            let mut token_calls = ink_e2e::create_call_builder::<MockPsp22>(token);
+            let bob = ink_e2e::address::<ink::env::DefaultEnvironment>(ink_e2e::Sr25519Keyring::Bob);
+            
+            client.call(&ink_e2e::alice(), &token_calls.mint(treasury, 1000)).submit().await?;
+            apply_config(&mut client, treasury, vec![
+                ConfigChange::AddAcceptedAsset(token),
+                ConfigChange::SetOutflowLimits(Some(OutflowLimits {
+                    max_single_spend: None,
+                    window_blocks: 100,
+                    max_window_outflow: None,
+                    type_budgets: vec![],
+                    token_budgets: vec![(token, 500)],
+                })),
+            ]).await;
+            
+            let spend = |amount| TreasurySpend {
+                recipient: SpendRecipient::Single(bob),
+                amount,
+                asset: Asset::Psp22(token),
+                pot: None,
+            };
+            let first = pass_spend(&mut client, treasury, spend(300)).await;
+            let second = pass_spend(&mut client, treasury, spend(300)).await;
+            
+            let executed = client.call(&ink_e2e::alice(), &calls.execute_proposal(first)).submit().await?;
+            assert_eq!(executed.return_value(), Ok(()));
+            let breached = client.call(&ink_e2e::alice(), &calls.execute_proposal(second)).dry_run().await?;
+            assert_eq!(breached.return_value(), Err(Error::OutflowLimitExceeded));
+            
+            // The failed execution reverts, so the breach is reported separately to keep the pause
+            client.call(&ink_e2e::alice(), &calls.report_outflow_breach(second)).submit().await?;
+            let paused = client.call(&ink_e2e::alice(), &calls.is_outflow_paused()).dry_run().await?;
+            assert!(paused.return_value());
+            Ok(())
         }
     }
 }
@@ -0,0 +1,112 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+//! Minimal PSP22 token used by the treasury's end-to-end tests.
+
+pub use self::mock_psp22::{MockPsp22, PSP22Error, PSP22};
+
+#[ink::contract]
+pub mod mock_psp22 {
+    use ink::prelude::{string::String, vec::Vec};
+    use ink::primitives::H160;
+    use ink::storage::Mapping;
+
+    /// Errors returned by PSP22 token contracts
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
+    }
+
+    /// The subset of PSP22 the treasury calls
+    #[ink::trait_definition]
+    pub trait PSP22 {
+        #[ink(message)]
+        fn balance_of(&self, owner: H160) -> u128;
+
+        #[ink(message)]
+        fn allowance(&self, owner: H160, spender: H160) -> u128;
+
+        #[ink(message)]
+        fn transfer(&mut self, to: H160, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+        #[ink(message)]
+        fn transfer_from(&mut self, from: H160, to: H160, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+        #[ink(message)]
+        fn approve(&mut self, spender: H160, value: u128) -> Result<(), PSP22Error>;
+    }
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MockPsp22 {
+        balances: Mapping<H160, u128>,
+        allowances: Mapping<(H160, H160), u128>,
+    }
+
+    impl MockPsp22 {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Credit `value` tokens to `to` out of thin air
+        #[ink(message)]
+        pub fn mint(&mut self, to: H160, value: u128) {
+            let balance = self.balance_of(to);
+            self.balances.insert(to, &balance.saturating_add(value));
+        }
+
+        fn move_tokens(&mut self, from: H160, to: H160, value: u128) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, &to_balance.saturating_add(value));
+            Ok(())
+        }
+    }
+
+    impl PSP22 for MockPsp22 {
+        #[ink(message)]
+        fn balance_of(&self, owner: H160) -> u128 {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: H160, spender: H160) -> u128 {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: H160, value: u128, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            let from = self.env().caller();
+            self.move_tokens(from, to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(&mut self, from: H160, to: H160, value: u128, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            let spender = self.env().caller();
+            let allowance = self.allowance(from, spender);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.move_tokens(from, to, value)?;
+            self.allowances.insert((from, spender), &(allowance - value));
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: H160, value: u128) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            self.allowances.insert((owner, spender), &value);
+            Ok(())
+        }
+    }
+}